@@ -0,0 +1,79 @@
+use num::BigUint;
+
+use crate::factorization::factorize;
+
+pub fn mobius(n: &BigUint) -> i8 {
+    factorize(n.clone()).mobius()
+}
+
+pub fn euler_phi(n: &BigUint) -> BigUint {
+    factorize(n.clone()).euler_totient()
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigUint, Integer, One};
+
+    use super::{euler_phi, mobius};
+
+    fn brute_mobius(n: u32) -> i8 {
+        let mut res = 1;
+        let mut m = n;
+        for p in 2..=n {
+            if Integer::is_multiple_of(&m, &p) {
+                m /= p;
+                if Integer::is_multiple_of(&m, &p) {
+                    return 0;
+                }
+                res = -res;
+            }
+        }
+        res
+    }
+
+    fn brute_phi(n: u32) -> BigUint {
+        let n = BigUint::from(n);
+        let count = num::range(BigUint::one(), n.clone() + 1u8)
+            .filter(|a| a.gcd(&n).is_one())
+            .count();
+        BigUint::from(count)
+    }
+
+    #[test]
+    fn test_mobius() {
+        assert_eq!(mobius(&BigUint::from(1u8)), 1);
+        assert_eq!(mobius(&BigUint::from(30u8)), -1);
+        assert_eq!(mobius(&BigUint::from(12u8)), 0);
+        assert_eq!(mobius(&BigUint::from(6u8)), 1);
+        for n in 1..500u32 {
+            assert_eq!(
+                mobius(&BigUint::from(n)),
+                brute_mobius(n),
+                "Failure on {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_euler_phi() {
+        assert_eq!(euler_phi(&BigUint::from(1u8)), BigUint::one());
+        assert_eq!(euler_phi(&BigUint::from(36u8)), BigUint::from(12u8));
+        for n in 1..500u32 {
+            assert_eq!(
+                euler_phi(&BigUint::from(n)),
+                brute_phi(n),
+                "Failure on {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_inputs() {
+        // 1000003 * 1000033
+        let n = BigUint::from(1000003u64 * 1000033u64);
+        assert_eq!(mobius(&n), 1);
+        assert_eq!(euler_phi(&n), BigUint::from(1000002u64 * 1000032u64));
+    }
+}
@@ -1,9 +1,22 @@
+pub mod models;
+pub mod pairing;
+pub mod schoof;
+
 use core::panic;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+
+use num::{BigInt, BigUint, Integer, One, Signed, Zero};
 
-use crate::fields::Field;
+use crate::{
+    factorization::trial_factorization,
+    fields::{Field, PrimeField},
+};
 
 // y^2 + a_1 x y + a_3 y = x^3 + a_2 x^2 + a_4 x + a_6
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GeneralForm<F> {
     a_1: F,
     a_2: F,
@@ -12,12 +25,121 @@ pub struct GeneralForm<F> {
     a_6: F,
 }
 
-#[derive(Debug, Clone)]
+// The quantities attached to a general form, as in Silverman III.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurveInvariants<F> {
+    pub b_2: F,
+    pub b_4: F,
+    pub b_6: F,
+    pub b_8: F,
+    pub c_4: F,
+    pub c_6: F,
+    pub discriminant: F,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Point<F> {
     Point((F, F)),
     Infinity,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    Truncated,
+    TrailingBytes,
+    InvalidTag(u8),
+    // A coordinate or coefficient is not smaller than the modulus
+    NotReduced,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "input is truncated"),
+            DecodeError::TrailingBytes => write!(f, "input has trailing bytes"),
+            DecodeError::InvalidTag(t) => write!(f, "invalid point tag {}", t),
+            DecodeError::NotReduced => write!(f, "field element is not reduced"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// Exactly n field elements of modulus_bytes each
+fn decode_elements<F: PrimeField>(bytes: &[u8], n: usize) -> Result<Vec<F>, DecodeError> {
+    let width = F::modulus_bytes();
+    match bytes.len().cmp(&(n * width)) {
+        Ordering::Less => return Err(DecodeError::Truncated),
+        Ordering::Greater => return Err(DecodeError::TrailingBytes),
+        Ordering::Equal => {}
+    }
+    bytes
+        .chunks(width)
+        .map(|chunk| F::from_bytes_le(chunk).ok_or(DecodeError::NotReduced))
+        .collect()
+}
+
+const INFINITY_TAG: u8 = 0;
+const AFFINE_TAG: u8 = 1;
+
+impl<F> Point<F>
+where
+    F: PrimeField,
+{
+    // A tag byte, followed by x and y for affine points, so 1 or 1 + 2 modulus_bytes bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Point::Infinity => vec![INFINITY_TAG],
+            Point::Point((x, y)) => {
+                let mut res = vec![AFFINE_TAG];
+                res.extend(x.to_bytes_le());
+                res.extend(y.to_bytes_le());
+                res
+            }
+        }
+    }
+
+    // Does not check that the point lies on any particular curve
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        match bytes.split_first() {
+            None => Err(DecodeError::Truncated),
+            Some((&INFINITY_TAG, [])) => Ok(Point::Infinity),
+            Some((&INFINITY_TAG, _)) => Err(DecodeError::TrailingBytes),
+            Some((&AFFINE_TAG, rest)) => {
+                let mut coords = decode_elements::<F>(rest, 2)?;
+                let y = coords.pop().unwrap();
+                let x = coords.pop().unwrap();
+                Ok(Point::Point((x, y)))
+            }
+            Some((&tag, _)) => Err(DecodeError::InvalidTag(tag)),
+        }
+    }
+}
+
+// Infinity comes first, then affine points lexicographically
+impl<F> Ord for Point<F>
+where
+    F: Field + Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Point::Infinity, Point::Infinity) => Ordering::Equal,
+            (Point::Infinity, _) => Ordering::Less,
+            (_, Point::Infinity) => Ordering::Greater,
+            (Point::Point(p), Point::Point(q)) => p.cmp(q),
+        }
+    }
+}
+
+impl<F> PartialOrd for Point<F>
+where
+    F: Field + Ord,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 macro_rules! accessor_impl {
     ($access:ident) => {
         fn $access(&self) -> F {
@@ -36,30 +158,57 @@ where
     accessor_impl!(a_4);
     accessor_impl!(a_6);
 
+    pub fn from_coeffs(a_1: F, a_2: F, a_3: F, a_4: F, a_6: F) -> Self {
+        GeneralForm {
+            a_1,
+            a_2,
+            a_3,
+            a_4,
+            a_6,
+        }
+    }
+
     // Utilities, refer to Silverman, arithmetic of elliptic curves, 2nd Ed, page 42
-    fn b_2(&self) -> F {
-        self.a_1.square() + self.a_4.scale(4)
+    pub fn b_2(&self) -> F {
+        self.a_1.square() + self.a_2.scale(4)
     }
 
-    fn b_4(&self) -> F {
+    pub fn b_4(&self) -> F {
         self.a_4.scale(2) + self.a_1() * self.a_3()
     }
 
-    fn b_6(&self) -> F {
+    pub fn b_6(&self) -> F {
         self.a_3.square() + self.a_6.scale(4)
     }
 
-    fn b_8(&self) -> F {
+    pub fn b_8(&self) -> F {
         self.a_1.square() * self.a_6() + (self.a_2() * self.a_6()).scale(4)
             - self.a_1() * self.a_3() * self.a_4()
             + self.a_2() * self.a_3.square()
             - self.a_4.square()
     }
 
-    fn c_4(&self) -> F {
+    pub fn c_4(&self) -> F {
         self.b_2().square() - self.b_4().scale(24)
     }
 
+    pub fn c_6(&self) -> F {
+        -self.b_2().square() * self.b_2() + (self.b_2() * self.b_4()).scale(36)
+            - self.b_6().scale(216)
+    }
+
+    pub fn invariants(&self) -> CurveInvariants<F> {
+        CurveInvariants {
+            b_2: self.b_2(),
+            b_4: self.b_4(),
+            b_6: self.b_6(),
+            b_8: self.b_8(),
+            c_4: self.c_4(),
+            c_6: self.c_6(),
+            discriminant: self.discriminant(),
+        }
+    }
+
     pub fn is_on_curve(&self, p: &Point<F>) -> bool {
         match p {
             Point::Infinity => true,
@@ -70,6 +219,14 @@ where
         }
     }
 
+    // Keeps the candidates lying on the curve, in order
+    pub fn filter_on_curve(&self, candidates: impl IntoIterator<Item = Point<F>>) -> Vec<Point<F>> {
+        candidates
+            .into_iter()
+            .filter(|p| self.is_on_curve(p))
+            .collect()
+    }
+
     pub fn discriminant(&self) -> F {
         -self.b_2().square() * self.b_8()
             - (self.b_4().square() * self.b_4()).scale(8)
@@ -142,4 +299,700 @@ where
             }
         }
     }
+
+    // Group law, refer to Silverman, arithmetic of elliptic curves, 2nd Ed, page 53
+    pub fn add(&self, p: &Point<F>, q: &Point<F>) -> Point<F> {
+        let ((x_1, y_1), (x_2, y_2)) = match (p, q) {
+            (Point::Infinity, _) => return q.clone(),
+            (_, Point::Infinity) => return p.clone(),
+            (Point::Point(p), Point::Point(q)) => (p, q),
+        };
+
+        let (lambda, nu) = match self.line((x_1, y_1), (x_2, y_2)) {
+            Some(line) => line,
+            None => return Point::Infinity,
+        };
+
+        let x_3 = lambda.square() + self.a_1() * &lambda - self.a_2() - x_1.clone() - x_2.clone();
+        let y_3 = -(lambda + self.a_1()) * &x_3 - nu - self.a_3();
+        Point::Point((x_3, y_3))
+    }
+
+    // The line y = lambda x + nu through two affine points, the tangent if they are equal
+    // None for the vertical line, when the points are each other's negation
+    fn line(&self, (x_1, y_1): (&F, &F), (x_2, y_2): (&F, &F)) -> Option<(F, F)> {
+        if x_1 == x_2 && (y_1.clone() + y_2 + self.a_1() * x_2 + self.a_3()).is_zero() {
+            return None;
+        }
+
+        if x_1 != x_2 {
+            let den = (x_2.clone() - x_1).invert().unwrap();
+            Some((
+                (y_2.clone() - y_1) * &den,
+                (y_1.clone() * x_2 - y_2.clone() * x_1) * den,
+            ))
+        } else {
+            let den = (y_1.scale(2) + self.a_1() * x_1 + self.a_3())
+                .invert()
+                .unwrap();
+            Some((
+                (x_1.square().scale(3) + (self.a_2() * x_1).scale(2) + self.a_4()
+                    - self.a_1() * y_1)
+                    * &den,
+                (-x_1.square() * x_1 + self.a_4() * x_1 + self.a_6().scale(2) - self.a_3() * y_1)
+                    * den,
+            ))
+        }
+    }
+
+    pub fn scalar_mul(&self, n: &BigUint, p: &Point<F>) -> Point<F> {
+        let mut acc = Point::Infinity;
+        for i in (0..n.bits()).rev() {
+            acc = self.add(&acc, &acc);
+            if n.bit(i) {
+                acc = self.add(&acc, p);
+            }
+        }
+        acc
+    }
+
+    // As above, negative scalars multiply the negation
+    fn signed_scalar_mul(&self, n: &BigInt, p: &Point<F>) -> Point<F> {
+        let res = self.scalar_mul(n.magnitude(), p);
+        if n.is_negative() {
+            self.negate(&res)
+        } else {
+            res
+        }
+    }
+
+    // Hasse's theorem, #E = q + 1 - t with |t| <= 2 sqrt(q)
+    // The bound on t is floor(sqrt(4 q)), which can exceed 2 floor(sqrt(q))
+    pub fn hasse_interval(&self) -> (BigUint, BigUint) {
+        let q = F::order();
+        let bound = (q.clone() * 4u8).sqrt();
+        (q.clone() + 1u8 - &bound, q + 1u8 + bound)
+    }
+
+    pub fn is_valid_order(&self, candidate: &BigUint) -> bool {
+        let (low, high) = self.hasse_interval();
+        &low <= candidate && candidate <= &high
+    }
+
+    // Naive point count, including the point at infinity. Assumes F is a prime field.
+    // For every x we count the y with y^2 + (a_1 x + a_3) y = x^3 + a_2 x^2 + a_4 x + a_6
+    pub fn count_points(&self) -> BigUint {
+        let q = F::order();
+        let two = BigUint::from(2u8);
+        let mut count = BigUint::one();
+
+        let mut x = F::zero();
+        for _ in num::range(BigUint::zero(), q.clone()) {
+            let b = self.a_1() * &x + self.a_3();
+            let c = x.square() * &x + self.a_2() * x.square() + self.a_4() * &x + self.a_6();
+
+            if F::characteristic() == two {
+                let mut y = F::zero();
+                for _ in num::range(BigUint::zero(), q.clone()) {
+                    if y.square() + b.clone() * &y == c {
+                        count += 1u8;
+                    }
+                    y += F::one();
+                }
+            } else {
+                // Solutions are (-b +- sqrt(b^2 + 4c)) / 2
+                let disc = b.square() + c.scale(4);
+                if disc.is_zero() {
+                    count += 1u8;
+                } else if disc.pow_biguint(&((q.clone() - 1u8) / 2u8)).is_one() {
+                    count += 2u8;
+                }
+            }
+
+            x += F::one();
+        }
+        count
+    }
+}
+
+impl<F> GeneralForm<F>
+where
+    F: Field + Hash,
+{
+    // Order of p, searching for a multiple of it in the Hasse interval with baby-step giant-step
+    pub fn point_order(&self, p: &Point<F>) -> BigUint {
+        if *p == Point::Infinity {
+            return BigUint::one();
+        }
+
+        // #E = q + 1 - t with |t| <= 2 sqrt(q) < width
+        let q = F::order();
+        let width = BigInt::from(q.sqrt() * 2u8 + 2u8);
+        let m = width.sqrt() + 1u8;
+
+        // Baby steps jP for 0 <= j <= m
+        let mut baby_steps = HashMap::new();
+        let mut baby = Point::Infinity;
+        for j in num::range_inclusive(BigInt::zero(), m.clone()) {
+            let next = self.add(&baby, p);
+            baby_steps.entry(baby).or_insert(j);
+            baby = next;
+        }
+
+        // Giant steps (q + 1 + k (2m + 1)) P for |k (2m + 1)| <= width + m
+        let step = m.clone() * 2u8 + 1u8;
+        let giant = self.signed_scalar_mul(&step, p);
+        let k_bound = (width + &m).div_ceil(&step);
+        let start = BigInt::from(q.clone()) + 1u8 - &k_bound * &step;
+        let mut current = self.signed_scalar_mul(&start, p);
+        let mut multiple = None;
+
+        for k in num::range_inclusive(-k_bound.clone(), k_bound) {
+            let base = BigInt::from(q.clone()) + 1u8 + k * &step;
+            let candidates = [
+                baby_steps.get(&current).map(|j| base.clone() - j),
+                baby_steps
+                    .get(&self.negate(&current))
+                    .map(|j| base.clone() + j),
+            ];
+            multiple = candidates
+                .iter()
+                .flatten()
+                .find(|c| c.is_positive())
+                .and_then(|c| c.to_biguint());
+
+            if multiple.is_some() {
+                break;
+            }
+            current = self.add(&current, &giant);
+        }
+
+        // Now strip any prime factors not needed to kill p
+        let mut order = multiple.expect("The group order is in the Hasse interval");
+        for (prime, mult) in trial_factorization(order.clone()) {
+            for _ in 0..mult {
+                let quot = order.clone() / &prime;
+                if self.scalar_mul(&quot, p) == Point::Infinity {
+                    order = quot;
+                } else {
+                    break;
+                }
+            }
+        }
+        order
+    }
+}
+
+impl<F> GeneralForm<F>
+where
+    F: PrimeField,
+{
+    // The y with y^2 + (a_1 x + a_3) y = x^3 + a_2 x^2 + a_4 x + a_6, assumes odd characteristic
+    fn ordinates(&self, x: &F) -> Option<(F, F)> {
+        let b = self.a_1() * x + self.a_3();
+        let c = x.square() * x + self.a_2() * x.square() + self.a_4() * x + self.a_6();
+        let root = (b.square() + c.scale(4)).sqrt()?;
+        let half = F::integer_embed(2).invert().unwrap();
+        Some(((root.clone() - &b) * &half, (-root - b) * half))
+    }
+
+    // Encodes an affine point as x and the parity of y
+    // None for infinity, and for the (general form only) points sharing the parity of y with their negation
+    pub fn compress(&self, p: &Point<F>) -> Option<(F, bool)> {
+        match p {
+            Point::Infinity => None,
+            Point::Point((x, y)) => {
+                let (y_1, y_2) = self.ordinates(x)?;
+                let other = if *y == y_1 { y_2 } else { y_1 };
+                let y_is_odd = y.to_biguint().is_odd();
+                if other != *y && other.to_biguint().is_odd() == y_is_odd {
+                    return None;
+                }
+                Some((x.clone(), y_is_odd))
+            }
+        }
+    }
+
+    // t = 0 mod p, with #E = p + 1 - t. Counts points naively, except in characteristic 2 and 3
+    // where supersingular is the same as j = 0
+    pub fn is_supersingular(&self) -> bool {
+        let p = F::characteristic();
+        if p == BigUint::from(2u8) || p == BigUint::from(3u8) {
+            return self.c_4().is_zero();
+        }
+
+        // Hasse bounds |t| < p, so the trace vanishes exactly
+        self.count_points() == p + 1u8
+    }
+
+    // Every affine point, by increasing x. Brute forces y only in characteristic 2
+    pub fn all_affine_points(&self) -> Vec<Point<F>> {
+        let q = F::order();
+        let mut res = Vec::new();
+        let mut x = F::zero();
+        for _ in num::range(BigUint::zero(), q.clone()) {
+            if F::characteristic() == BigUint::from(2u8) {
+                let mut candidates = Vec::new();
+                let mut y = F::zero();
+                for _ in num::range(BigUint::zero(), q.clone()) {
+                    candidates.push(Point::Point((x.clone(), y.clone())));
+                    y += F::one();
+                }
+                res.extend(self.filter_on_curve(candidates));
+            } else if let Some((y_1, y_2)) = self.ordinates(&x) {
+                res.push(Point::Point((x.clone(), y_1.clone())));
+                if y_1 != y_2 {
+                    res.push(Point::Point((x.clone(), y_2)));
+                }
+            }
+            x += F::one();
+        }
+        res
+    }
+
+    // a_1, a_2, a_3, a_4, a_6, each in modulus_bytes bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        [&self.a_1, &self.a_2, &self.a_3, &self.a_4, &self.a_6]
+            .iter()
+            .flat_map(|a| a.to_bytes_le())
+            .collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut coeffs = decode_elements::<F>(bytes, 5)?.into_iter();
+        let mut next = || coeffs.next().unwrap();
+        Ok(Self::from_coeffs(next(), next(), next(), next(), next()))
+    }
+
+    // None if x is not the abscissa of a point with the given parity
+    pub fn decompress(&self, x: &F, y_is_odd: bool) -> Option<Point<F>> {
+        let (y_1, y_2) = self.ordinates(x)?;
+        [y_1, y_2]
+            .iter()
+            .find(|y| y.to_biguint().is_odd() == y_is_odd)
+            .map(|y| Point::Point((x.clone(), y.clone())))
+    }
+}
+
+// Writes the non zero terms of the sum coeff * monomial, omitting unit coefficients
+fn write_terms<F>(f: &mut fmt::Formatter<'_>, terms: &[(F, &str)]) -> fmt::Result
+where
+    F: Field + fmt::Display,
+{
+    let mut first = true;
+    for (coeff, monomial) in terms.iter().filter(|(c, _)| !c.is_zero()) {
+        if !first {
+            write!(f, " + ")?;
+        }
+        first = false;
+
+        if monomial.is_empty() {
+            write!(f, "{}", coeff)?;
+        } else if coeff.is_one() {
+            write!(f, "{}", monomial)?;
+        } else {
+            write!(f, "{} {}", coeff, monomial)?;
+        }
+    }
+    Ok(())
+}
+
+impl<F> fmt::Display for GeneralForm<F>
+where
+    F: Field + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_terms(
+            f,
+            &[(F::one(), "y^2"), (self.a_1(), "xy"), (self.a_3(), "y")],
+        )?;
+        write!(f, " = ")?;
+        write_terms(
+            f,
+            &[
+                (F::one(), "x^3"),
+                (self.a_2(), "x^2"),
+                (self.a_4(), "x"),
+                (self.a_6(), ""),
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use num::{BigUint, Integer, One};
+
+    use super::{DecodeError, GeneralForm, Point};
+    use crate::factorization::trial_factorization;
+    use crate::fields::primefields::PrimeField4999;
+    use crate::fields::{Field, PrimeField};
+    use crate::rings::Ring;
+    use rand::SeedableRng;
+
+    fn curve(coeffs: [i32; 5]) -> GeneralForm<PrimeField4999> {
+        let [a_1, a_2, a_3, a_4, a_6] = coeffs.map(PrimeField4999::integer_embed);
+        GeneralForm::from_coeffs(a_1, a_2, a_3, a_4, a_6)
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(curve([0, 0, 0, 1, 1]).to_string(), "y^2 = x^3 + x + 1");
+        assert_eq!(curve([0, 0, 0, 0, 0]).to_string(), "y^2 = x^3");
+        assert_eq!(
+            curve([1, 2, 3, 4, 5]).to_string(),
+            "y^2 + xy + 3 y = x^3 + 2 x^2 + 4 x + 5"
+        );
+        assert_eq!(
+            curve([2, 3, 4, 5, -1]).to_string(),
+            "y^2 + 2 xy + 4 y = x^3 + 3 x^2 + 5 x + 4998"
+        );
+    }
+
+    #[test]
+    fn serialization() {
+        let curves = vec![
+            curve([0, 0, 0, 1, 1]),
+            curve([1, 2, 3, 4, 5]),
+            curve([2, 3, 4, 5, -1]),
+        ];
+        for c in curves {
+            let bytes = c.to_bytes();
+            assert_eq!(bytes.len(), 5 * PrimeField4999::modulus_bytes());
+            assert_eq!(GeneralForm::from_bytes(&bytes), Ok(c.clone()));
+            assert_eq!(
+                GeneralForm::<PrimeField4999>::from_bytes(&bytes[..bytes.len() - 1]),
+                Err(DecodeError::Truncated)
+            );
+
+            let mut points = some_points(&c, 5);
+            points.push(Point::Infinity);
+            for p in points {
+                let bytes = p.to_bytes();
+                assert_eq!(Point::from_bytes(&bytes), Ok(p.clone()));
+                assert_eq!(
+                    Point::<PrimeField4999>::from_bytes(&bytes[..bytes.len() - 1]),
+                    Err(DecodeError::Truncated)
+                );
+            }
+        }
+
+        assert_eq!(Point::<PrimeField4999>::Infinity.to_bytes(), vec![0]);
+        let p = Point::Point((
+            PrimeField4999::integer_embed(1),
+            PrimeField4999::integer_embed(2),
+        ));
+        assert_eq!(p.to_bytes(), vec![1, 1, 0, 2, 0]);
+        assert_eq!(
+            Point::<PrimeField4999>::from_bytes(&[2, 1, 0, 2, 0]),
+            Err(DecodeError::InvalidTag(2))
+        );
+        assert_eq!(
+            Point::<PrimeField4999>::from_bytes(&[1, 1, 0, 2, 0, 0]),
+            Err(DecodeError::TrailingBytes)
+        );
+        // 5000 does not fit below the modulus
+        assert_eq!(
+            Point::<PrimeField4999>::from_bytes(&[1, 0x88, 0x13, 2, 0]),
+            Err(DecodeError::NotReduced)
+        );
+    }
+
+    // The first few affine points of the curve, by brute force
+    fn some_points(curve: &GeneralForm<PrimeField4999>, n: usize) -> Vec<Point<PrimeField4999>> {
+        let mut res = Vec::new();
+        for x in 0..4999 {
+            for y in 0..4999 {
+                let p = Point::Point((
+                    PrimeField4999::integer_embed(x),
+                    PrimeField4999::integer_embed(y),
+                ));
+                if curve.is_on_curve(&p) {
+                    res.push(p);
+                    if res.len() == n {
+                        return res;
+                    }
+                    break;
+                }
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn group_law() {
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5]] {
+            let e = curve(coeffs);
+            let points = some_points(&e, 5);
+            for p in &points {
+                assert_eq!(e.add(p, &Point::Infinity), *p);
+                assert_eq!(e.add(p, &e.negate(p)), Point::Infinity);
+                for q in &points {
+                    let sum = e.add(p, q);
+                    assert!(e.is_on_curve(&sum));
+                    assert_eq!(sum, e.add(q, p));
+                    for r in &points {
+                        assert_eq!(e.add(&sum, r), e.add(p, &e.add(q, r)));
+                    }
+                }
+
+                let mut acc = Point::Infinity;
+                for i in 0..20u32 {
+                    assert_eq!(e.scalar_mul(&BigUint::from(i), p), acc);
+                    acc = e.add(&acc, p);
+                }
+            }
+        }
+    }
+
+    // Brute force over all pairs, with machine integers
+    fn naive_count(coeffs: [u64; 5], p: u64) -> BigUint {
+        let [a_1, a_2, a_3, a_4, a_6] = coeffs;
+        let mut count = 1u32;
+        for x in 0..p {
+            let rhs = (x * x % p * x + a_2 * x % p * x + a_4 * x + a_6) % p;
+            for y in 0..p {
+                if (y * y + a_1 * x % p * y + a_3 * y) % p == rhs {
+                    count += 1;
+                }
+            }
+        }
+        BigUint::from(count)
+    }
+
+    #[test]
+    fn count_points() {
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5], [0, 0, 1, 0, 0]] {
+            let e = curve(coeffs.map(|c| c as i32));
+            assert_eq!(e.count_points(), naive_count(coeffs, 4999));
+        }
+    }
+
+    #[test]
+    fn invariants() {
+        const ROUNDS: usize = 100;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..ROUNDS {
+            let [a_1, a_2, a_3, a_4, a_6] = [(); 5].map(|_| PrimeField4999::random(&mut rng));
+            let e = GeneralForm::from_coeffs(a_1, a_2, a_3, a_4, a_6);
+            let inv = e.invariants();
+
+            assert_eq!(
+                inv.discriminant.scale(1728),
+                inv.c_4.pow(3) - inv.c_6.square()
+            );
+            // 4 b_8 = b_2 b_6 - b_4^2
+            assert_eq!(
+                inv.b_8.scale(4),
+                inv.b_2.clone() * &inv.b_6 - inv.b_4.square()
+            );
+            assert_eq!(inv.c_6, e.c_6());
+            assert_eq!(inv.discriminant, e.discriminant());
+        }
+
+        // y^2 = x^3 + a x + b has c_4 = -48 a, c_6 = -864 b and discriminant -16 (4 a^3 + 27 b^2)
+        let inv = curve([0, 0, 0, 2, 3]).invariants();
+        assert_eq!(inv.c_4, PrimeField4999::integer_embed(-96));
+        assert_eq!(inv.c_6, PrimeField4999::integer_embed(-2592));
+        assert_eq!(
+            inv.discriminant,
+            PrimeField4999::integer_embed(-16 * (32 + 243))
+        );
+    }
+
+    #[test]
+    fn hasse_interval() {
+        // 4999 + 1 -+ floor(2 sqrt(4999)) = 5000 -+ 141
+        let e = curve([0, 0, 0, 1, 1]);
+        assert_eq!(
+            e.hasse_interval(),
+            (BigUint::from(4859u32), BigUint::from(5141u32))
+        );
+
+        for coeffs in [
+            [0, 0, 0, 1, 1],
+            [1, 2, 3, 4, 5],
+            [0, 0, 1, 0, 0],
+            [0, 0, 0, 2, 3],
+        ] {
+            let e = curve(coeffs);
+            assert!(e.is_valid_order(&e.count_points()));
+
+            let (low, high) = e.hasse_interval();
+            assert!(e.is_valid_order(&low));
+            assert!(e.is_valid_order(&high));
+            assert!(!e.is_valid_order(&(low - 1u8)));
+            assert!(!e.is_valid_order(&(high + 1u8)));
+            assert!(!e.is_valid_order(&BigUint::one()));
+        }
+    }
+
+    #[test]
+    fn is_supersingular() {
+        // 4999 = 3 mod 4, so y^2 = x^3 + a x is supersingular for every a
+        for a in 1..20 {
+            assert!(curve([0, 0, 0, a, 0]).is_supersingular());
+        }
+        // But 4999 = 1 mod 3, and y^2 = x^3 + 1 is ordinary
+        assert!(!curve([0, 0, 0, 0, 1]).is_supersingular());
+        assert!(!curve([0, 0, 0, 1, 1]).is_supersingular());
+        assert!(!curve([1, 2, 3, 4, 5]).is_supersingular());
+    }
+
+    #[test]
+    fn all_affine_points() {
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5], [0, 0, 1, 0, 0]] {
+            let e = curve(coeffs);
+            let points = e.all_affine_points();
+            assert_eq!(BigUint::from(points.len() + 1), e.count_points());
+            assert!(points.iter().all(|p| e.is_on_curve(p)));
+            for p in some_points(&e, 5) {
+                assert!(points.contains(&p));
+            }
+        }
+    }
+
+    #[test]
+    fn filter_on_curve() {
+        let e = curve([0, 0, 0, 1, 1]);
+        let on_curve = some_points(&e, 5);
+        let off_curve: Vec<_> = on_curve
+            .iter()
+            .map(|p| match p {
+                Point::Point((x, y)) => {
+                    Point::Point((x.clone(), y.clone() + PrimeField4999::one()))
+                }
+                Point::Infinity => unreachable!(),
+            })
+            .filter(|p| !e.is_on_curve(p))
+            .collect();
+        assert!(!off_curve.is_empty());
+
+        let candidates = on_curve
+            .iter()
+            .cloned()
+            .chain(off_curve)
+            .chain(std::iter::once(Point::Infinity));
+        let mut expected = on_curve.clone();
+        expected.push(Point::Infinity);
+        assert_eq!(e.filter_on_curve(candidates), expected);
+    }
+
+    #[test]
+    fn point_order() {
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5], [0, 0, 1, 0, 0]] {
+            let e = curve(coeffs);
+            let count = e.count_points();
+            assert_eq!(e.point_order(&Point::Infinity), BigUint::one());
+            for p in some_points(&e, 5) {
+                let order = e.point_order(&p);
+                assert_eq!(e.scalar_mul(&order, &p), Point::Infinity);
+                assert!(count.is_multiple_of(&order));
+                for (prime, _) in trial_factorization(order.clone()) {
+                    assert_ne!(e.scalar_mul(&(order.clone() / prime), &p), Point::Infinity);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn points_in_hash_set() {
+        let e = curve([0, 0, 0, 1, 1]);
+        let points = some_points(&e, 10);
+        let mut set: HashSet<_> = points.iter().cloned().collect();
+        assert_eq!(set.len(), points.len());
+
+        for p in &points {
+            assert!(!set.insert(p.clone()));
+            assert!(set.contains(&e.add(p, &Point::Infinity)));
+        }
+
+        assert!(set.insert(Point::Infinity));
+        assert!(!set.insert(Point::Infinity));
+        assert_eq!(set.len(), points.len() + 1);
+    }
+
+    #[test]
+    fn points_ordering() {
+        let e = curve([0, 0, 0, 1, 1]);
+        let mut points = some_points(&e, 10);
+        points.push(Point::Infinity);
+        points.extend(points.clone().into_iter().map(|p| e.negate(&p)));
+
+        let mut sorted = points.clone();
+        sorted.sort();
+        let mut reversed = points;
+        reversed.reverse();
+        reversed.sort();
+        assert_eq!(sorted, reversed);
+
+        assert_eq!(sorted[0], Point::Infinity);
+        assert_eq!(sorted[1], Point::Infinity);
+        for pair in sorted[2..].windows(2) {
+            match (&pair[0], &pair[1]) {
+                (Point::Point((x_1, y_1)), Point::Point((x_2, y_2))) => {
+                    assert!(x_1 < x_2 || (x_1 == x_2 && y_1 <= y_2));
+                }
+                _ => panic!("Infinity should come first"),
+            }
+        }
+    }
+
+    #[test]
+    fn compression_round_trip() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5], [0, 0, 1, 0, 0]] {
+            let e = curve(coeffs);
+            assert!(e.compress(&Point::Infinity).is_none());
+
+            let mut found = 0;
+            for _ in 0..1000 {
+                let x = PrimeField4999::random(&mut rng);
+                let y_is_odd = rand::Rng::gen(&mut rng);
+                let p = match e.decompress(&x, y_is_odd) {
+                    Some(p) => p,
+                    None => continue,
+                };
+                found += 1;
+
+                assert!(e.is_on_curve(&p));
+                if let Some((x_c, odd)) = e.compress(&p) {
+                    assert_eq!((x_c, odd), (x.clone(), y_is_odd));
+                    assert_eq!(e.decompress(&x, odd), Some(p));
+                }
+            }
+            assert!(found > 0);
+        }
+
+        // In short Weierstrass form every affine point compresses
+        let e = curve([0, 0, 0, 1, 1]);
+        for p in some_points(&e, 20) {
+            let (x, odd) = e.compress(&p).unwrap();
+            assert_eq!(e.decompress(&x, odd), Some(p));
+        }
+    }
+
+    #[test]
+    fn decompress_rejects_non_abscissas() {
+        let e = curve([0, 0, 0, 1, 1]);
+        for x in 0..50 {
+            let x = PrimeField4999::integer_embed(x);
+            let on_curve = (0..4999).any(|y| {
+                e.is_on_curve(&Point::Point((x.clone(), PrimeField4999::integer_embed(y))))
+            });
+            let decompressed = [false, true].iter().any(|b| e.decompress(&x, *b).is_some());
+            assert_eq!(on_curve, decompressed);
+        }
+
+        // y = 0 is even, so only one parity decompresses
+        let e = curve([0, 0, 0, -1, 0]);
+        let zero = PrimeField4999::zero();
+        assert_eq!(
+            e.decompress(&zero, false),
+            Some(Point::Point((zero.clone(), zero.clone())))
+        );
+        assert!(e.decompress(&zero, true).is_none());
+    }
 }
@@ -0,0 +1,451 @@
+// Other models of elliptic curves, and the birational maps to the general form
+// Everything here assumes the characteristic is not 2
+use num::BigUint;
+
+use crate::{
+    ec::{GeneralForm, Point},
+    fields::{Field, PrimeField},
+};
+
+// B y^2 = x^3 + A x^2 + x
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MontgomeryCurve<F> {
+    a: F,
+    b: F,
+}
+
+impl<F> MontgomeryCurve<F>
+where
+    F: Field,
+{
+    pub fn new(a: F, b: F) -> Self {
+        if (b.clone() * (a.square() - F::integer_embed(4))).is_zero() {
+            panic!("Singular Montgomery curve, need B (A^2 - 4) != 0");
+        }
+        MontgomeryCurve { a, b }
+    }
+
+    pub fn a(&self) -> F {
+        self.a.clone()
+    }
+
+    pub fn b(&self) -> F {
+        self.b.clone()
+    }
+
+    pub fn is_on_curve(&self, p: &Point<F>) -> bool {
+        match p {
+            Point::Infinity => true,
+            Point::Point((x, y)) => {
+                self.b() * y.square() == x.square() * x + self.a() * x.square() + x
+            }
+        }
+    }
+
+    pub fn negate(&self, p: &Point<F>) -> Point<F> {
+        match p {
+            Point::Infinity => Point::Infinity,
+            Point::Point((x, y)) => Point::Point((x.clone(), -y.clone())),
+        }
+    }
+
+    pub fn add(&self, p: &Point<F>, q: &Point<F>) -> Point<F> {
+        let ((x_1, y_1), (x_2, y_2)) = match (p, q) {
+            (Point::Infinity, _) => return q.clone(),
+            (_, Point::Infinity) => return p.clone(),
+            (Point::Point(p), Point::Point(q)) => (p, q),
+        };
+
+        if x_1 == x_2 && (y_1.clone() + y_2).is_zero() {
+            return Point::Infinity;
+        }
+
+        let lambda = if x_1 != x_2 {
+            (y_2.clone() - y_1) * (x_2.clone() - x_1).invert().unwrap()
+        } else {
+            (x_1.square().scale(3) + (self.a() * x_1).scale(2) + F::one())
+                * (self.b() * y_1).scale(2).invert().unwrap()
+        };
+
+        let x_3 = self.b() * lambda.square() - self.a() - x_1.clone() - x_2.clone();
+        let y_3 = lambda * (x_1.clone() - &x_3) - y_1.clone();
+        Point::Point((x_3, y_3))
+    }
+
+    // y^2 = x^3 + (A / B) x^2 + (1 / B^2) x, via (x, y) -> (x / B, y / B)
+    pub fn to_weierstrass(&self) -> GeneralForm<F> {
+        let b_inv = self.b().invert().unwrap();
+        GeneralForm::from_coeffs(
+            F::zero(),
+            self.a() * &b_inv,
+            F::zero(),
+            b_inv.square(),
+            F::zero(),
+        )
+    }
+
+    // Maps a point of this curve to the curve given by to_weierstrass
+    pub fn point_to_weierstrass(&self, p: &Point<F>) -> Point<F> {
+        let b_inv = self.b().invert().unwrap();
+        match p {
+            Point::Infinity => Point::Infinity,
+            Point::Point((x, y)) => Point::Point((x.clone() * &b_inv, y.clone() * b_inv)),
+        }
+    }
+
+    // Inverse of point_to_weierstrass
+    pub fn point_from_weierstrass(&self, p: &Point<F>) -> Point<F> {
+        match p {
+            Point::Infinity => Point::Infinity,
+            Point::Point((x, y)) => Point::Point((x.clone() * self.b(), y.clone() * self.b())),
+        }
+    }
+}
+
+impl<F> MontgomeryCurve<F>
+where
+    F: PrimeField,
+{
+    // A Montgomery model exists iff the curve has a point (alpha, _) of order 2 with
+    // 3 alpha^2 + 2 a alpha + b a square, after completing the square to y^2 = x^3 + a x^2 + b x + c
+    // The point of order 2 is found by brute force, so F should be small
+    pub fn from_weierstrass(curve: &GeneralForm<F>) -> Option<MontgomeryMap<F>> {
+        if curve.discriminant().is_zero() {
+            return None;
+        }
+
+        let half = F::integer_embed(2).invert().unwrap();
+        let quarter = half.square();
+        let (c_2, c_1, c_0) = (
+            curve.b_2() * &quarter,
+            curve.b_4() * &half,
+            curve.b_6() * &quarter,
+        );
+
+        let mut alpha = F::zero();
+        for _ in num::range(BigUint::from(0u8), F::order()) {
+            let value = alpha.square() * &alpha
+                + c_2.clone() * alpha.square()
+                + c_1.clone() * &alpha
+                + &c_0;
+            if value.is_zero() {
+                let derivative = alpha.square().scale(3) + (c_2.clone() * &alpha).scale(2) + &c_1;
+                if let Some(s) = derivative.sqrt() {
+                    let s_inv = s.invert().unwrap();
+                    let montgomery = MontgomeryCurve::new((alpha.scale(3) + &c_2) * &s_inv, s_inv);
+                    return Some(MontgomeryMap {
+                        montgomery,
+                        alpha,
+                        s,
+                        half_a_1: curve.a_1() * &half,
+                        half_a_3: curve.a_3() * half,
+                    });
+                }
+            }
+            alpha += F::one();
+        }
+        None
+    }
+}
+
+// (x, y) -> ((x - alpha) / s, (y + (a_1 x + a_3) / 2) / s), from a general form to a Montgomery curve
+#[derive(Debug, Clone)]
+pub struct MontgomeryMap<F> {
+    pub montgomery: MontgomeryCurve<F>,
+    alpha: F,
+    s: F,
+    half_a_1: F,
+    half_a_3: F,
+}
+
+impl<F> MontgomeryMap<F>
+where
+    F: Field,
+{
+    pub fn to_montgomery(&self, p: &Point<F>) -> Point<F> {
+        match p {
+            Point::Infinity => Point::Infinity,
+            Point::Point((x, y)) => {
+                let s_inv = self.s.invert().unwrap();
+                let eta = y.clone() + self.half_a_1.clone() * x + &self.half_a_3;
+                Point::Point(((x.clone() - &self.alpha) * &s_inv, eta * s_inv))
+            }
+        }
+    }
+
+    pub fn to_weierstrass(&self, p: &Point<F>) -> Point<F> {
+        match p {
+            Point::Infinity => Point::Infinity,
+            Point::Point((u, v)) => {
+                let x = self.s.clone() * u + &self.alpha;
+                let y = self.s.clone() * v - self.half_a_1.clone() * &x - &self.half_a_3;
+                Point::Point((x, y))
+            }
+        }
+    }
+}
+
+// a x^2 + y^2 = 1 + d x^2 y^2, points are affine with identity (0, 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TwistedEdwardsCurve<F> {
+    a: F,
+    d: F,
+}
+
+impl<F> TwistedEdwardsCurve<F>
+where
+    F: Field,
+{
+    pub fn new(a: F, d: F) -> Self {
+        if (a.clone() * &d * (a.clone() - &d)).is_zero() {
+            panic!("Singular twisted Edwards curve, need a d (a - d) != 0");
+        }
+        TwistedEdwardsCurve { a, d }
+    }
+
+    pub fn a(&self) -> F {
+        self.a.clone()
+    }
+
+    pub fn d(&self) -> F {
+        self.d.clone()
+    }
+
+    pub fn identity() -> (F, F) {
+        (F::zero(), F::one())
+    }
+
+    pub fn is_on_curve(&self, (x, y): &(F, F)) -> bool {
+        self.a() * x.square() + y.square() == F::one() + self.d() * x.square() * y.square()
+    }
+
+    pub fn negate(&self, (x, y): &(F, F)) -> (F, F) {
+        (-x.clone(), y.clone())
+    }
+
+    // Unified addition law, complete when a is a square and d is not
+    // Otherwise panics on the exceptional pairs where a denominator vanishes
+    pub fn add(&self, (x_1, y_1): &(F, F), (x_2, y_2): &(F, F)) -> (F, F) {
+        let t = self.d() * x_1 * x_2 * y_1 * y_2;
+        let x_den = (F::one() + &t)
+            .invert()
+            .expect("Exceptional pair for the Edwards addition law");
+        let y_den = (F::one() - t)
+            .invert()
+            .expect("Exceptional pair for the Edwards addition law");
+        (
+            (x_1.clone() * y_2 + y_1.clone() * x_2) * x_den,
+            (y_1.clone() * y_2 - self.a() * x_1 * x_2) * y_den,
+        )
+    }
+
+    // A = 2 (a + d) / (a - d), B = 4 / (a - d)
+    pub fn to_montgomery(&self) -> MontgomeryCurve<F> {
+        let inv = (self.a() - self.d()).invert().unwrap();
+        MontgomeryCurve::new((self.a() + self.d()).scale(2) * &inv, inv.scale(4))
+    }
+
+    // a = (A + 2) / B, d = (A - 2) / B
+    pub fn from_montgomery(curve: &MontgomeryCurve<F>) -> Self {
+        let two = F::integer_embed(2);
+        let b_inv = curve.b().invert().unwrap();
+        TwistedEdwardsCurve::new((curve.a() + &two) * &b_inv, (curve.a() - two) * b_inv)
+    }
+
+    pub fn to_weierstrass(&self) -> GeneralForm<F> {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    // (x, y) -> ((1 + y) / (1 - y), (1 + y) / ((1 - y) x)), on the curve given by to_montgomery
+    pub fn point_to_montgomery(&self, p: &(F, F)) -> Point<F> {
+        let (x, y) = p;
+        if x.is_zero() {
+            return if y.is_one() {
+                Point::Infinity
+            } else {
+                Point::Point((F::zero(), F::zero()))
+            };
+        }
+
+        let u = (F::one() + y) * (F::one() - y.clone()).invert().unwrap();
+        let v = u.clone() * x.invert().unwrap();
+        Point::Point((u, v))
+    }
+
+    // (u, v) -> (u / v, (u - 1) / (u + 1))
+    // None for the points sent to infinity, which only exist when d is a square
+    pub fn point_from_montgomery(&self, p: &Point<F>) -> Option<(F, F)> {
+        match p {
+            Point::Infinity => Some(Self::identity()),
+            Point::Point((u, v)) => {
+                if u.is_zero() && v.is_zero() {
+                    return Some((F::zero(), -F::one()));
+                }
+                let x = u.clone() * v.invert()?;
+                let y = (u.clone() - F::one()) * (u.clone() + F::one()).invert()?;
+                Some((x, y))
+            }
+        }
+    }
+
+    pub fn point_to_weierstrass(&self, p: &(F, F)) -> Point<F> {
+        let montgomery = self.to_montgomery();
+        montgomery.point_to_weierstrass(&self.point_to_montgomery(p))
+    }
+
+    pub fn point_from_weierstrass(&self, p: &Point<F>) -> Option<(F, F)> {
+        let montgomery = self.to_montgomery();
+        self.point_from_montgomery(&montgomery.point_from_weierstrass(p))
+    }
+}
+
+impl<F> TwistedEdwardsCurve<F>
+where
+    F: PrimeField,
+{
+    // Through a Montgomery model, returning the map to it as well
+    pub fn from_weierstrass(curve: &GeneralForm<F>) -> Option<(Self, MontgomeryMap<F>)> {
+        let map = MontgomeryCurve::from_weierstrass(curve)?;
+        Some((Self::from_montgomery(&map.montgomery), map))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MontgomeryCurve, TwistedEdwardsCurve};
+    use crate::ec::{GeneralForm, Point};
+    use crate::fields::primefields::PrimeField4999;
+    use crate::fields::Field;
+    use crate::rings::Ring;
+
+    fn el(x: i32) -> PrimeField4999 {
+        PrimeField4999::integer_embed(x)
+    }
+
+    fn montgomery_points(curve: &MontgomeryCurve<PrimeField4999>) -> Vec<Point<PrimeField4999>> {
+        let b_inv = curve.b().invert().unwrap();
+        let mut res = vec![Point::Infinity];
+        for x in 0..4999 {
+            let x = el(x);
+            let rhs = (x.square() * &x + curve.a() * x.square() + &x) * &b_inv;
+            if let Some(y) = rhs.sqrt() {
+                res.push(Point::Point((x.clone(), y.clone())));
+                res.push(Point::Point((x, -y)));
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn montgomery_round_trip() {
+        let curve = MontgomeryCurve::new(el(6), el(3));
+        let weierstrass = curve.to_weierstrass();
+        let points = montgomery_points(&curve);
+        for p in points.iter() {
+            assert!(curve.is_on_curve(p));
+            let q = curve.point_to_weierstrass(p);
+            assert!(weierstrass.is_on_curve(&q));
+            assert_eq!(&curve.point_from_weierstrass(&q), p);
+        }
+
+        // The maps are group homomorphisms
+        for (p, q) in points.iter().zip(points.iter().skip(7)).step_by(50) {
+            let sum = curve.add(p, q);
+            assert!(curve.is_on_curve(&sum));
+            assert_eq!(
+                curve.point_to_weierstrass(&sum),
+                weierstrass.add(
+                    &curve.point_to_weierstrass(p),
+                    &curve.point_to_weierstrass(q)
+                )
+            );
+            assert_eq!(
+                curve.point_to_weierstrass(&curve.add(p, p)),
+                weierstrass.add(
+                    &curve.point_to_weierstrass(p),
+                    &curve.point_to_weierstrass(p)
+                )
+            );
+            assert_eq!(curve.add(p, &curve.negate(p)), Point::Infinity);
+        }
+    }
+
+    #[test]
+    fn weierstrass_round_trip() {
+        // General form curves with non zero a_1 and a_3, not all having a point of order 2
+        let mut found = 0;
+        for a_6 in 0..20 {
+            let curve = GeneralForm::from_coeffs(el(1), el(5), el(2), el(7), el(a_6));
+            let map = match MontgomeryCurve::from_weierstrass(&curve) {
+                Some(map) => map,
+                None => continue,
+            };
+            found += 1;
+
+            for p in curve
+                .all_affine_points()
+                .into_iter()
+                .chain([Point::Infinity])
+            {
+                let q = map.to_montgomery(&p);
+                assert!(map.montgomery.is_on_curve(&q));
+                assert_eq!(map.to_weierstrass(&q), p);
+            }
+        }
+        assert!(found > 0);
+
+        // Completing the square gives y^2 = x^3 + x + 3, and the cubic has no root mod 4999
+        let curve = GeneralForm::from_coeffs(el(0), el(0), el(0), el(1), el(3));
+        assert!((0..4999).all(|x| !(el(x).pow(3) + el(x) + el(3)).is_zero()));
+        assert!(MontgomeryCurve::from_weierstrass(&curve).is_none());
+    }
+
+    #[test]
+    fn edwards_round_trip() {
+        let curve = TwistedEdwardsCurve::new(el(2), el(3));
+        let montgomery = curve.to_montgomery();
+        assert_eq!(TwistedEdwardsCurve::from_montgomery(&montgomery), curve);
+
+        let mut points = vec![TwistedEdwardsCurve::identity()];
+        for y in 0..4999 {
+            let y = el(y);
+            let x_squared = (PrimeField4999::one() - y.square())
+                * (el(2) - el(3) * y.square()).invert().unwrap();
+            if let Some(x) = x_squared.sqrt() {
+                points.push((x.clone(), y.clone()));
+                points.push((-x, y));
+            }
+        }
+
+        let weierstrass = curve.to_weierstrass();
+        for p in points.iter() {
+            assert!(curve.is_on_curve(p));
+            let q = curve.point_to_montgomery(p);
+            assert!(montgomery.is_on_curve(&q));
+            assert_eq!(curve.point_from_montgomery(&q).as_ref(), Some(p));
+            let r = curve.point_to_weierstrass(p);
+            assert!(weierstrass.is_on_curve(&r));
+            assert_eq!(curve.point_from_weierstrass(&r).as_ref(), Some(p));
+        }
+
+        for (p, q) in points.iter().zip(points.iter().skip(3)).step_by(50) {
+            // The law is not complete for this curve, skip the exceptional pairs
+            let (t_x, t_y) = (p.0.clone() * &q.0, p.1.clone() * &q.1);
+            let t = el(3) * t_x * t_y;
+            if (PrimeField4999::one() + &t).is_zero() || (PrimeField4999::one() - t).is_zero() {
+                continue;
+            }
+            let sum = curve.add(p, q);
+            assert!(curve.is_on_curve(&sum));
+            assert_eq!(
+                curve.point_to_montgomery(&sum),
+                montgomery.add(&curve.point_to_montgomery(p), &curve.point_to_montgomery(q))
+            );
+        }
+        let p = &points[1];
+        assert_eq!(
+            curve.add(p, &curve.negate(p)),
+            TwistedEdwardsCurve::identity()
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// The Weil pairing through Miller's algorithm, refer to Washington, elliptic curves, 2nd Ed, section 11.4
+// We only handle the case where the roots of unity and the whole torsion are defined over F
+use num::{BigUint, Integer, Zero};
+
+use crate::{
+    ec::{GeneralForm, Point},
+    fields::PrimeField,
+};
+
+impl<F> GeneralForm<F>
+where
+    F: PrimeField,
+{
+    // l(r) / v(r), with l the line through a and b and v the vertical line through a + b
+    // None if r is a zero or a pole of either of them
+    fn line_ratio(&self, a: &Point<F>, b: &Point<F>, r: &(F, F)) -> Option<F> {
+        let (a, b) = match (a, b) {
+            (Point::Point(a), Point::Point(b)) => (a, b),
+            _ => return Some(F::one()),
+        };
+
+        let (num, den) = match self.line((&a.0, &a.1), (&b.0, &b.1)) {
+            // a + b is infinity, and v is constant
+            None => (r.0.clone() - &a.0, F::one()),
+            Some((lambda, nu)) => {
+                match self.add(&Point::Point(a.clone()), &Point::Point(b.clone())) {
+                    Point::Point((x_3, _)) => (r.1.clone() - lambda * &r.0 - nu, r.0.clone() - x_3),
+                    Point::Infinity => unreachable!(),
+                }
+            }
+        };
+
+        if num.is_zero() {
+            return None;
+        }
+        Some(num * den.invert()?)
+    }
+
+    // f(r), where f has divisor n (p) - (n p) - (n - 1) (O), by double and add
+    fn miller(&self, p: &Point<F>, n: &BigUint, r: &(F, F)) -> Option<F> {
+        let mut t = p.clone();
+        let mut f = F::one();
+        for i in (0..n.bits() - 1).rev() {
+            f = f.square() * self.line_ratio(&t, &t, r)?;
+            t = self.add(&t, &t);
+            if n.bit(i) {
+                f *= self.line_ratio(&t, p, r)?;
+                t = self.add(&t, p);
+            }
+        }
+        Some(f)
+    }
+
+    // e_n(p, q) = f_p(D_q) / f_q(D_p), with D_p = (p + s) - (s) and D_q = (q) - (O)
+    // None if s makes some evaluation hit a zero or a pole
+    fn weil_pairing_with(
+        &self,
+        p: &Point<F>,
+        q: &Point<F>,
+        s: &Point<F>,
+        n: &BigUint,
+    ) -> Option<F> {
+        let affine = |point: Point<F>| match point {
+            Point::Point(coords) => Some(coords),
+            Point::Infinity => None,
+        };
+
+        let minus_s = self.negate(s);
+        let num = self.miller(p, n, &affine(self.add(q, &minus_s))?)?
+            * self.miller(q, n, &affine(s.clone())?)?;
+        let den =
+            self.miller(p, n, &affine(minus_s)?)? * self.miller(q, n, &affine(self.add(p, s))?)?;
+        Some(num * den.invert()?)
+    }
+
+    // The Weil pairing of two points of order dividing n, a n-th root of unity
+    // None unless both points are n-torsion and F contains the n-th roots of unity. Assumes odd characteristic
+    pub fn weil_pairing(&self, p: &Point<F>, q: &Point<F>, order: &BigUint) -> Option<F> {
+        if order.is_zero() {
+            panic!("Order must be positive");
+        }
+
+        if !(F::order() - 1u8).is_multiple_of(order)
+            || self.scalar_mul(order, p) != Point::Infinity
+            || self.scalar_mul(order, q) != Point::Infinity
+        {
+            return None;
+        }
+
+        if *p == Point::Infinity || *q == Point::Infinity {
+            return Some(F::one());
+        }
+
+        // The auxiliary point only needs to avoid finitely many bad points, try them by increasing x
+        let mut x = F::zero();
+        for _ in num::range(BigUint::zero(), F::order()) {
+            if let Some((y, _)) = self.ordinates(&x) {
+                let s = Point::Point((x.clone(), y));
+                if let Some(e) = self.weil_pairing_with(p, q, &s, order) {
+                    return Some(e);
+                }
+            }
+            x += F::one();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigUint;
+    use rand::{Rng, SeedableRng};
+
+    use crate::{
+        ec::{GeneralForm, Point},
+        fields::{primefields::PrimeField4999, Field},
+        rings::Ring,
+    };
+
+    fn point(x: i32, y: i32) -> Point<PrimeField4999> {
+        Point::Point((
+            PrimeField4999::integer_embed(x),
+            PrimeField4999::integer_embed(y),
+        ))
+    }
+
+    // y^2 = x^3 + 16 x + 39 has 4900 points, with the full 7-torsion, and 7 divides 4998
+    fn curve() -> GeneralForm<PrimeField4999> {
+        let [a_1, a_2, a_3, a_4, a_6] = [0, 0, 0, 16, 39].map(PrimeField4999::integer_embed);
+        GeneralForm::from_coeffs(a_1, a_2, a_3, a_4, a_6)
+    }
+
+    #[test]
+    fn weil_pairing() {
+        let curve = curve();
+        let seven = BigUint::from(7u8);
+        let (p, q) = (point(88, 1683), point(260, 1516));
+        assert!(curve.is_on_curve(&p) && curve.is_on_curve(&q));
+
+        // Non degenerate, so a primitive 7th root of unity
+        let e = curve.weil_pairing(&p, &q, &seven).unwrap();
+        assert!(!e.is_one());
+        assert!(e.pow(7).is_one());
+
+        // Alternating
+        assert!(curve.weil_pairing(&p, &p, &seven).unwrap().is_one());
+        assert_eq!(
+            curve.weil_pairing(&q, &p, &seven).unwrap(),
+            e.invert().unwrap()
+        );
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..5 {
+            let (a, b) = (rng.gen_range(1..7u32), rng.gen_range(1..7u32));
+            let a_p = curve.scalar_mul(&BigUint::from(a), &p);
+            let b_q = curve.scalar_mul(&BigUint::from(b), &q);
+            assert_eq!(curve.weil_pairing(&a_p, &q, &seven).unwrap(), e.pow(a));
+            assert_eq!(
+                curve.weil_pairing(&a_p, &b_q, &seven).unwrap(),
+                e.pow(a * b)
+            );
+            // Linear dependence gives one
+            assert!(curve.weil_pairing(&a_p, &p, &seven).unwrap().is_one());
+        }
+
+        assert!(curve
+            .weil_pairing(&Point::Infinity, &q, &seven)
+            .unwrap()
+            .is_one());
+        assert!(curve
+            .weil_pairing(&p, &Point::Infinity, &seven)
+            .unwrap()
+            .is_one());
+
+        // Not 5-torsion, and 5 does not divide 4998 anyway
+        assert_eq!(curve.weil_pairing(&p, &q, &BigUint::from(5u8)), None);
+    }
+}
@@ -0,0 +1,402 @@
+// Schoof's algorithm, computing t mod l for small primes l by letting Frobenius act on the l-torsion,
+// refer to Washington, elliptic curves, 2nd Ed, section 4.5
+// We work on the isomorphic short form y^2 = x^3 + a x + b, so the characteristic must not be 2 or 3
+use num::{BigInt, BigUint, Integer, One, ToPrimitive};
+
+use crate::{
+    ec::{GeneralForm, Point},
+    fields::Field,
+    num::crt,
+    poly::DensePolynomial,
+    primes::Primes,
+};
+
+type Poly<F> = DensePolynomial<F>;
+
+// Division polynomials of y^2 = x^3 + a x + b, as polynomials in x only
+// psi_n itself for odd n, and psi_n / y for even n
+struct DivisionPolynomials<F> {
+    a: F,
+    b: F,
+    f: Poly<F>,
+    cache: Vec<Poly<F>>,
+}
+
+impl<F> DivisionPolynomials<F>
+where
+    F: Field,
+{
+    fn new(a: F, b: F) -> Self {
+        let f = Poly::new([b.clone(), a.clone(), F::zero(), F::one()]);
+        DivisionPolynomials {
+            a,
+            b,
+            f,
+            cache: Vec::new(),
+        }
+    }
+
+    fn initial(&self, n: usize) -> Poly<F> {
+        let (a, b) = (self.a.clone(), self.b.clone());
+        match n {
+            0 => Poly::zero(),
+            1 => Poly::new([F::one()]),
+            2 => Poly::new([F::integer_embed(2)]),
+            // 3 x^4 + 6 a x^2 + 12 b x - a^2
+            3 => Poly::new([
+                -a.square(),
+                b.scale(12),
+                a.scale(6),
+                F::zero(),
+                F::integer_embed(3),
+            ]),
+            // 4 (x^6 + 5 a x^4 + 20 b x^3 - 5 a^2 x^2 - 4 a b x - 8 b^2 - a^3)
+            4 => Poly::new([
+                (-b.square().scale(8) - a.square() * &a).scale(4),
+                (-(a.clone() * &b).scale(4)).scale(4),
+                (-a.square().scale(5)).scale(4),
+                b.scale(80),
+                a.scale(20),
+                F::zero(),
+                F::integer_embed(4),
+            ]),
+            _ => unreachable!(),
+        }
+    }
+
+    fn get(&mut self, n: usize) -> Poly<F> {
+        while self.cache.len() <= n {
+            let k = self.cache.len();
+            let next = if k <= 4 {
+                self.initial(k)
+            } else {
+                self.recurrence(k)
+            };
+            self.cache.push(next);
+        }
+        self.cache[n].clone()
+    }
+
+    fn recurrence(&self, n: usize) -> Poly<F> {
+        let psi = |k: usize| &self.cache[k];
+        let cube = |p: &Poly<F>| p.mult(p).mult(p);
+        let f_squared = self.f.mult(&self.f);
+        let m = n / 2;
+
+        if n.is_odd() {
+            // psi_{2m + 1} = psi_{m + 2} psi_m^3 - psi_{m - 1} psi_{m + 1}^3
+            // where the two even factors bring a y^4 = f^2
+            let (first, second) = (
+                psi(m + 2).mult(&cube(psi(m))),
+                psi(m - 1).mult(&cube(psi(m + 1))),
+            );
+            if m.is_even() {
+                f_squared.mult(&first).add(&second.negate())
+            } else {
+                first.add(&f_squared.mult(&second).negate())
+            }
+        } else {
+            // psi_{2m} = psi_m (psi_{m + 2} psi_{m - 1}^2 - psi_{m - 2} psi_{m + 1}^2) / 2y
+            let square = |p: &Poly<F>| p.mult(p);
+            let half = Poly::new([F::integer_embed(2).invert().unwrap()]);
+            let inner = psi(m + 2)
+                .mult(&square(psi(m - 1)))
+                .add(&psi(m - 2).mult(&square(psi(m + 1))).negate());
+            psi(m).mult(&inner).mult(&half)
+        }
+    }
+}
+
+// The ring F[x, y] / (h(x), y^2 - f(x)), where h divides the l-th division polynomial
+// Elements of E[l] are written (X(x), Y(x) y), so only polynomials in x are stored
+struct TorsionRing<F> {
+    h: Poly<F>,
+    f: Poly<F>,
+    a: F,
+}
+
+impl<F> TorsionRing<F>
+where
+    F: Field,
+{
+    fn new(h: Poly<F>, f: &Poly<F>, a: F) -> Self {
+        let h = h.normalize_monic();
+        let f = reduce(f, &h);
+        TorsionRing { h, f, a }
+    }
+
+    fn mul(&self, p: &Poly<F>, q: &Poly<F>) -> Poly<F> {
+        reduce(&p.mult(q), &self.h)
+    }
+
+    fn pow(&self, p: &Poly<F>, exp: &BigUint) -> Poly<F> {
+        let mut acc = Poly::new([F::one()]);
+        for i in (0..exp.bits()).rev() {
+            acc = self.mul(&acc, &acc);
+            if exp.bit(i) {
+                acc = self.mul(&acc, p);
+            }
+        }
+        acc
+    }
+
+    // Err with a proper factor of h when p is a zero divisor
+    fn invert(&self, p: &Poly<F>) -> Result<Poly<F>, Poly<F>> {
+        assert!(!p.is_zero(), "Division by zero in the torsion ring");
+
+        // Invariant: r_i = s_i p mod h
+        let (mut r_0, mut r_1) = (self.h.clone(), p.clone());
+        let (mut s_0, mut s_1) = (Poly::zero(), Poly::new([F::one()]));
+        while !r_1.is_zero() {
+            let (q, r) = r_0.div_quotient_rem(&r_1);
+            let s = s_0.add(&q.mult(&s_1).negate());
+            r_0 = std::mem::replace(&mut r_1, r);
+            s_0 = std::mem::replace(&mut s_1, s);
+        }
+
+        if r_0.degree() == Some(0) {
+            let inv = Poly::new([r_0.leading().invert().unwrap()]);
+            Ok(reduce(&s_0.mult(&inv), &self.h))
+        } else {
+            Err(r_0.normalize_monic())
+        }
+    }
+
+    fn sub(&self, p: &Poly<F>, q: &Poly<F>) -> Poly<F> {
+        p.add(&q.negate())
+    }
+
+    // The chord and tangent rules, with y^2 replaced by f
+    fn add(&self, p: &Point<Poly<F>>, q: &Point<Poly<F>>) -> Result<Point<Poly<F>>, Poly<F>> {
+        let ((x_1, y_1), (x_2, y_2)) = match (p, q) {
+            (Point::Infinity, _) => return Ok(q.clone()),
+            (_, Point::Infinity) => return Ok(p.clone()),
+            (Point::Point(p), Point::Point(q)) => (p, q),
+        };
+
+        // lambda = lambda' y
+        let lambda = if x_1 == x_2 {
+            if y_1.add(y_2).is_zero() {
+                return Ok(Point::Infinity);
+            }
+            if y_1 != y_2 {
+                // Equal on some torsion points and opposite on the others
+                return match self.invert(&self.sub(y_1, y_2)) {
+                    Err(factor) => Err(factor),
+                    Ok(_) => unreachable!("Points with the same x have y equal up to sign"),
+                };
+            }
+            // (3 x^2 + a) / 2 y = (3 x^2 + a) y / 2 f
+            let num = self
+                .mul(x_1, x_1)
+                .mult(&Poly::new([F::integer_embed(3)]))
+                .add(&Poly::new([self.a.clone()]));
+            let den = self
+                .mul(y_1, &self.f)
+                .mult(&Poly::new([F::integer_embed(2)]));
+            self.mul(&num, &self.invert(&den)?)
+        } else {
+            let den = self.invert(&self.sub(x_2, x_1))?;
+            self.mul(&self.sub(y_2, y_1), &den)
+        };
+
+        let lambda_squared = self.mul(&self.mul(&lambda, &lambda), &self.f);
+        let x_3 = self.sub(&self.sub(&lambda_squared, x_1), x_2);
+        let y_3 = self.sub(&self.mul(&lambda, &self.sub(x_1, &x_3)), y_1);
+        Ok(Point::Point((x_3, y_3)))
+    }
+
+    fn scalar_mul(&self, n: usize, p: &Point<Poly<F>>) -> Result<Point<Poly<F>>, Poly<F>> {
+        let mut acc = Point::Infinity;
+        for i in (0..usize::BITS - n.leading_zeros()).rev() {
+            acc = self.add(&acc, &acc)?;
+            if (n >> i) & 1 == 1 {
+                acc = self.add(&acc, p)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    // (x^(q^k), y^(q^k)), with y^(q^k) = y f^((q^k - 1) / 2)
+    fn frobenius(&self, k: u32) -> Point<Poly<F>> {
+        let q_k = F::order().pow(k);
+        let x = Poly::new([F::zero(), F::one()]);
+        Point::Point((self.pow(&x, &q_k), self.pow(&self.f, &((q_k - 1u8) / 2u8))))
+    }
+
+    // The c in [0, l) with phi^2 + q = c phi on the points cut out by h
+    fn trace(&self, l: usize) -> Result<usize, Poly<F>> {
+        let q_bar = (F::order() % l).to_usize().unwrap();
+        // (x, y) itself, x needs reducing once h is linear
+        let x = reduce(&Poly::new([F::zero(), F::one()]), &self.h);
+        let generic = Point::Point((x, Poly::new([F::one()])));
+
+        let lhs = self.add(&self.frobenius(2), &self.scalar_mul(q_bar, &generic)?)?;
+        if lhs == Point::Infinity {
+            return Ok(0);
+        }
+
+        let phi = self.frobenius(1);
+        let mut multiple = phi.clone();
+        for c in 1..l {
+            if multiple == lhs {
+                return Ok(c);
+            }
+            multiple = self.add(&multiple, &phi)?;
+        }
+        panic!("Frobenius does not satisfy its characteristic equation");
+    }
+}
+
+fn reduce<F: Field>(p: &Poly<F>, h: &Poly<F>) -> Poly<F> {
+    if p.degree() < h.degree() {
+        p.clone()
+    } else {
+        p.div_quotient_rem(h).1
+    }
+}
+
+impl<F> GeneralForm<F>
+where
+    F: Field,
+{
+    // Coefficients of y^2 = x^3 - 27 c_4 x - 54 c_6, isomorphic to the curve in characteristic > 3
+    fn short_form(&self) -> (F, F) {
+        let characteristic = F::characteristic();
+        if characteristic == BigUint::from(2u8) || characteristic == BigUint::from(3u8) {
+            panic!("Schoof is only implemented in characteristic greater than 3");
+        }
+        (-self.c_4().scale(27), -self.c_6().scale(54))
+    }
+
+    // t mod l for a prime l other than the characteristic, where #E = q + 1 - t
+    pub fn trace_of_frobenius_mod(&self, l: &BigUint) -> BigUint {
+        if l == &F::characteristic() {
+            panic!("l must differ from the characteristic");
+        }
+
+        let (a, b) = self.short_form();
+        let mut division_polynomials = DivisionPolynomials::new(a.clone(), b);
+        let f = division_polynomials.f.clone();
+
+        // As q is odd, t is even iff there is a point of order 2, i.e. f has a root
+        if l == &BigUint::from(2u8) {
+            let ring = TorsionRing::new(f.clone(), &f, a);
+            let x = Poly::new([F::zero(), F::one()]);
+            let x_q = ring.sub(&ring.pow(&x, &F::order()), &x);
+            let has_root = x_q.gcd(&f).degree() != Some(0);
+            return BigUint::from(!has_root as u8);
+        }
+
+        let l = l.to_usize().expect("l should be a small prime");
+        let mut h = division_polynomials.get(l);
+        loop {
+            match TorsionRing::new(h, &f, a.clone()).trace(l) {
+                Ok(t) => return BigUint::from(t),
+                // Frobenius also stabilises the smaller set of points
+                Err(factor) => h = factor,
+            }
+        }
+    }
+
+    // Schoof's algorithm, t is determined by its residues modulo primes whose product exceeds 4 sqrt(q)
+    pub fn point_count_schoof(&self) -> BigUint {
+        let q = F::order();
+        let bound = (q.clone() * 16u8).sqrt() + 1u8;
+
+        let mut congruences = Vec::new();
+        let mut modulus = BigUint::one();
+        for l in Primes::new() {
+            if modulus > bound {
+                break;
+            }
+            if l == F::characteristic() {
+                continue;
+            }
+            congruences.push((self.trace_of_frobenius_mod(&l), l.clone()));
+            modulus *= l;
+        }
+
+        // Lift t to the symmetric range, as |t| <= 2 sqrt(q) < modulus / 2
+        let t = BigInt::from(crt(&congruences).unwrap());
+        let modulus = BigInt::from(modulus);
+        let t = if t.clone() * 2u8 > modulus {
+            t - modulus
+        } else {
+            t
+        };
+        (BigInt::from(q) + 1u8 - t).to_biguint().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigUint, Integer};
+
+    use super::DivisionPolynomials;
+    use crate::ec::{GeneralForm, Point};
+    use crate::fields::primefields::PrimeField4999;
+    use crate::rings::Ring;
+
+    fn curve(coeffs: [i32; 5]) -> GeneralForm<PrimeField4999> {
+        let [a_1, a_2, a_3, a_4, a_6] = coeffs.map(PrimeField4999::integer_embed);
+        GeneralForm::from_coeffs(a_1, a_2, a_3, a_4, a_6)
+    }
+
+    #[test]
+    fn division_polynomials() {
+        // The roots of psi_n are the x coordinates of the points of order dividing n
+        let (a, b) = (
+            PrimeField4999::integer_embed(1),
+            PrimeField4999::integer_embed(1),
+        );
+        let short = curve([0, 0, 0, 1, 1]);
+        let mut psi = DivisionPolynomials::new(a, b);
+        for p in short.all_affine_points() {
+            // psi_n / y need not vanish at the points of order 2
+            let order = short.point_order(&p);
+            if order == BigUint::from(2u8) {
+                continue;
+            }
+            let x = match &p {
+                Point::Point((x, _)) => x.clone(),
+                Point::Infinity => unreachable!(),
+            };
+            for n in 3..12usize {
+                let vanishes = psi.get(n).evaluate(x.clone()).is_zero();
+                assert_eq!(vanishes, (BigUint::from(n) % &order) == BigUint::from(0u8));
+            }
+        }
+    }
+
+    #[test]
+    fn trace_residues() {
+        for coeffs in [
+            [0, 0, 0, 1, 1],
+            [1, 2, 3, 4, 5],
+            [0, 0, 0, 0, 7],
+            [0, 3, 0, 0, 1],
+        ] {
+            let curve = curve(coeffs);
+            let t = BigInt::from(5000u32) - BigInt::from(curve.count_points());
+            for l in [2u32, 3, 5, 7, 11, 13] {
+                let expected = t.mod_floor(&BigInt::from(l)).to_biguint().unwrap();
+                assert_eq!(
+                    curve.trace_of_frobenius_mod(&BigUint::from(l)),
+                    expected,
+                    "{:?} mod {}",
+                    coeffs,
+                    l
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn schoof_matches_naive_count() {
+        for coeffs in [[0, 0, 0, 1, 1], [1, 2, 3, 4, 5], [0, 0, 0, 2, 0]] {
+            let curve = curve(coeffs);
+            assert_eq!(curve.point_count_schoof(), curve.count_points());
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use core::fmt;
 use std::collections::BTreeMap;
 
+use lazy_static::lazy_static;
 use num::{bigint::RandBigInt, BigUint, Integer, One, Zero};
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
-use crate::primes::miller_rabin_with_randomness;
+use crate::primes::{miller_rabin_with_randomness, sieve};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Factorization {
@@ -47,6 +49,93 @@ impl Factorization {
         }
         self
     }
+
+    // Iterates over (prime, exponent) in increasing order of the prime
+    pub fn iter(&self) -> impl Iterator<Item = (&BigUint, u32)> {
+        self.map.iter().map(|(div, mult)| (div, *mult))
+    }
+
+    pub fn get(&self, p: &BigUint) -> u32 {
+        self.map.get(p).copied().unwrap_or(0)
+    }
+
+    // Exponent of p in the factorization, zero if p does not divide n
+    pub fn valuation(&self, p: &BigUint) -> u32 {
+        self.get(p)
+    }
+
+    // Removes up to times copies of p
+    pub fn quotient_by(&self, p: &BigUint, times: u32) -> Factorization {
+        let mut map = self.map.clone();
+        if let Some(mult) = map.get_mut(p) {
+            if *mult <= times {
+                map.remove(p);
+            } else {
+                *mult -= times;
+            }
+        }
+        Factorization { map }
+    }
+
+    // Least universal exponent, lcm of lambda(p^k) over the prime powers
+    pub fn carmichael_lambda(&self) -> BigUint {
+        let two = BigUint::from(2u8);
+        let mut res = BigUint::one();
+        for (div, mult) in &self.map {
+            let lambda = if *div == two && *mult >= 3 {
+                div.pow(*mult - 2)
+            } else {
+                (div.clone() - 1u8) * div.pow(*mult - 1)
+            };
+            res = res.lcm(&lambda);
+        }
+        res
+    }
+
+    pub fn euler_totient(&self) -> BigUint {
+        let mut res = BigUint::one();
+        for (div, mult) in &self.map {
+            res = res * (div.clone() - 1u8) * div.pow(*mult - 1);
+        }
+        res
+    }
+
+    // Zero if not squarefree, otherwise (-1)^(number of primes)
+    pub fn mobius(&self) -> i8 {
+        if self.map.values().any(|mult| *mult > 1) {
+            return 0;
+        }
+        if self.map.len().is_even() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    // The radical, every exponent set to one
+    pub fn squarefree_part(&self) -> Factorization {
+        Factorization::new(self.map.keys().map(|div| (div.clone(), 1)))
+    }
+
+    // Factorization of n^e
+    pub fn power(&self, e: u32) -> Factorization {
+        Factorization::new(self.map.iter().map(|(div, mult)| (div.clone(), mult * e)))
+    }
+}
+
+impl From<&Factorization> for BigUint {
+    fn from(fact: &Factorization) -> Self {
+        fact.n()
+    }
+}
+
+impl IntoIterator for Factorization {
+    type Item = (BigUint, u32);
+    type IntoIter = std::collections::btree_map::IntoIter<BigUint, u32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.map.into_iter()
+    }
 }
 
 fn term(div: &BigUint, mult: u32) -> String {
@@ -70,31 +159,113 @@ impl fmt::Display for Factorization {
     }
 }
 
-pub fn trial_factorization(mut n: BigUint) -> Factorization {
+// Primes below this are read off a sieve, above it we try odd numbers
+const TRIAL_SIEVE_BOUND: u64 = 1 << 12;
+
+lazy_static! {
+    static ref TRIAL_PRIMES: Vec<u64> = sieve(TRIAL_SIEVE_BOUND);
+}
+
+pub fn trial_factorization(n: BigUint) -> Factorization {
     if n == BigUint::zero() {
         panic!("Zero not allowed");
     }
 
+    let (fact, mut n) = trial_factorization_with_primes(n, &TRIAL_PRIMES);
     if n == BigUint::one() {
-        return Factorization::new(std::iter::empty());
+        return fact;
     }
 
+    // Every prime factor of n is now at least TRIAL_SIEVE_BOUND
     let mut res = BTreeMap::new();
-    let mut s = BigUint::from(2u8);
+    let mut s = BigUint::from(TRIAL_SIEVE_BOUND + 1);
     while n != BigUint::one() {
+        if s.clone() * s.clone() > n {
+            *res.entry(n).or_insert(0) += 1;
+            break;
+        }
+
         if n.is_multiple_of(&s) {
             *res.entry(s.clone()).or_insert(0) += 1;
-            n = n / s.clone();
+            n /= &s;
         } else {
-            s = s + 1u8;
+            s += 2u8;
         }
     }
 
-    Factorization::new(res)
+    fact.merge(Factorization::new(res))
 }
 
+// Prime-only trial division: divides out the primes of the list, returning the factorization
+// found and the remaining cofactor. primes must be the consecutive primes from 2 in increasing order.
+pub fn trial_factorization_with_primes(mut n: BigUint, primes: &[u64]) -> (Factorization, BigUint) {
+    if n == BigUint::zero() {
+        panic!("Zero not allowed");
+    }
+
+    let mut res = BTreeMap::new();
+    for p in primes {
+        if n == BigUint::one() {
+            break;
+        }
+
+        let p = BigUint::from(*p);
+        // Every smaller prime is divided out, so whatever is left is prime
+        if p.clone() * p.clone() > n {
+            *res.entry(n).or_insert(0) += 1;
+            n = BigUint::one();
+            break;
+        }
+
+        while n.is_multiple_of(&p) {
+            *res.entry(p.clone()).or_insert(0) += 1;
+            n /= &p;
+        }
+    }
+
+    (Factorization::new(res), n)
+}
+
+// Strips all prime factors up to bound, returning them and the remaining cofactor
+pub fn trial_divide_up_to(mut n: BigUint, bound: &BigUint) -> (Factorization, BigUint) {
+    if n == BigUint::zero() {
+        panic!("Zero not allowed");
+    }
+
+    let mut res = BTreeMap::new();
+    let mut s = BigUint::from(2u8);
+    while &s <= bound && n != BigUint::one() {
+        // Whatever is left is prime
+        if s.clone() * s.clone() > n {
+            if &n <= bound {
+                *res.entry(n).or_insert(0) += 1;
+                n = BigUint::one();
+            }
+            break;
+        }
+
+        if n.is_multiple_of(&s) {
+            *res.entry(s.clone()).or_insert(0) += 1;
+            n /= &s;
+        } else {
+            s += 1u8;
+        }
+    }
+
+    (Factorization::new(res), n)
+}
+
+pub fn is_smooth(n: &BigUint, bound: &BigUint) -> bool {
+    trial_divide_up_to(n.clone(), bound).1 == BigUint::one()
+}
+
+// None for n < 4, where there is no range to sample the constant from
 #[allow(non_snake_case)]
 pub fn pollard_rho_single_factor<R: Rng>(rng: &mut R, n: BigUint) -> Option<BigUint> {
+    if n < BigUint::from(4u8) {
+        return None;
+    }
+
     let s = rng.gen_biguint_range(&BigUint::zero(), &n);
     let b = rng.gen_biguint_range(&BigUint::one(), &(n.clone() - 2usize));
 
@@ -123,6 +294,43 @@ pub fn pollard_rho_single_factor<R: Rng>(rng: &mut R, n: BigUint) -> Option<BigU
     }
 }
 
+// Rho with f(x) = x^2 + c started at x0, giving up after max_iters steps
+// If the cycle closes without a factor, restarts with a random c and x0 from rng
+#[allow(non_snake_case)]
+pub fn pollard_rho_custom<R: Rng>(
+    rng: &mut R,
+    n: &BigUint,
+    c: &BigUint,
+    x0: &BigUint,
+    max_iters: usize,
+) -> Option<BigUint> {
+    if n <= &BigUint::from(3u8) {
+        return None;
+    }
+
+    let mut c = c % n;
+    let mut A = x0 % n;
+    let mut B = A.clone();
+    let two = BigUint::from(2u8);
+
+    for _ in 0..max_iters {
+        A = (A.modpow(&two, n) + &c) % n;
+        let fB = (B.modpow(&two, n) + &c) % n;
+        B = (fB.modpow(&two, n) + &c) % n;
+        let diff = if A >= B { &A - &B } else { &B - &A };
+        let g = diff.gcd(n);
+
+        if g == *n {
+            c = rng.gen_biguint_range(&BigUint::one(), &(n - 2u8));
+            A = rng.gen_biguint_below(n);
+            B = A.clone();
+        } else if !g.is_one() {
+            return Some(g);
+        }
+    }
+    None
+}
+
 pub fn pollard_rho_single_factor_repeat<R: Rng>(
     rng: &mut R,
     n: BigUint,
@@ -144,6 +352,24 @@ pub struct PollardRhoParameters {
     pub miller_rabin_rounds: usize,
 }
 
+impl PollardRhoParameters {
+    // Trial division up to about n^(1 / 4), capped at 2^20, and more restarts for larger inputs
+    pub fn for_bits(bits: u64) -> Self {
+        PollardRhoParameters {
+            trial_bound: BigUint::one() << (bits / 4).clamp(8, 20),
+            rho_rounds: 16 + 8 * bits as usize,
+            miller_rabin_rounds: 64,
+        }
+    }
+
+    pub fn default_for(n: &BigUint) -> Self {
+        Self::for_bits(n.bits())
+    }
+}
+
+// Inputs this small always go to trial division, whatever the parameters
+const RHO_MIN_INPUT: u32 = 1 << 8;
+
 pub fn pollard_rho_factorisation<R: Rng>(
     rng: &mut R,
     params: PollardRhoParameters,
@@ -160,28 +386,188 @@ pub fn pollard_rho_factorisation<R: Rng>(
         return Some(Factorization::new(vec![(n, 1)]));
     }
 
-    if n < params.trial_bound {
+    if n < params.trial_bound || n < BigUint::from(RHO_MIN_INPUT) {
         return Some(trial_factorization(n));
     }
 
+    // The factor found need not be prime
     let factor = pollard_rho_single_factor_repeat(rng, n.clone(), params.rho_rounds)?;
     let new_n = n / factor.clone();
     Some(
-        pollard_rho_factorisation(rng, params, new_n)?
-            .merge(Factorization::new(std::iter::once((factor, 1)))),
+        pollard_rho_factorisation(rng, params.clone(), new_n)?
+            .merge(pollard_rho_factorisation(rng, params, factor)?),
+    )
+}
+
+// Yields the prime factors of n, with multiplicity, splitting one cofactor at a time
+pub struct FactorStream<R: Rng> {
+    rng: R,
+    params: PollardRhoParameters,
+    // Factors whose primality is still unknown
+    pending: Vec<BigUint>,
+    // Primes found but not yielded yet
+    found: Vec<BigUint>,
+}
+
+impl<R: Rng> FactorStream<R> {
+    pub fn new(rng: R, params: PollardRhoParameters, n: BigUint) -> Self {
+        if n == BigUint::zero() {
+            panic!("Zero not allowed");
+        }
+
+        FactorStream {
+            rng,
+            params,
+            pending: vec![n],
+            found: Vec::new(),
+        }
+    }
+
+    fn trial_divide(&mut self, n: BigUint) {
+        for (p, mult) in trial_factorization(n) {
+            self.found.extend(std::iter::repeat_n(p, mult as usize));
+        }
+    }
+
+    // Product of the factors not yielded yet, one once the stream is done
+    pub fn remaining(&self) -> BigUint {
+        self.pending.iter().chain(self.found.iter()).product()
+    }
+}
+
+impl<R: Rng> Iterator for FactorStream<R> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(p) = self.found.pop() {
+                return Some(p);
+            }
+
+            let n = self.pending.pop()?;
+            if n.is_one() {
+                continue;
+            }
+
+            if n < self.params.trial_bound || n < BigUint::from(RHO_MIN_INPUT) {
+                self.trial_divide(n);
+                continue;
+            }
+
+            if miller_rabin_with_randomness(
+                &mut self.rng,
+                n.clone(),
+                self.params.miller_rabin_rounds,
+            )
+            .is_prime()
+            {
+                return Some(n);
+            }
+
+            match pollard_rho_single_factor_repeat(&mut self.rng, n.clone(), self.params.rho_rounds)
+            {
+                Some(factor) => {
+                    self.pending.push(n / &factor);
+                    self.pending.push(factor);
+                }
+                None => self.trial_divide(n),
+            }
+        }
+    }
+}
+
+// Below this factorize just uses trial division
+const FACTORIZE_TRIAL_BOUND: u32 = 1 << 16;
+
+// Factors n with sensible defaults, falling back to trial division if rho fails
+pub fn factorize(n: BigUint) -> Factorization {
+    factorize_with_rng(&mut rand::thread_rng(), n)
+}
+
+const FACTORIZE_DETERMINISTIC_SEED: u64 = 0;
+
+// As factorize, but with a fixed seed so the output is the same across runs
+pub fn factorize_deterministic(n: BigUint) -> Factorization {
+    factorize_with_rng(
+        &mut ChaCha20Rng::seed_from_u64(FACTORIZE_DETERMINISTIC_SEED),
+        n,
     )
 }
 
+fn factorize_with_rng<R: Rng>(rng: &mut R, n: BigUint) -> Factorization {
+    if n < BigUint::from(FACTORIZE_TRIAL_BOUND) {
+        return trial_factorization(n);
+    }
+
+    let params = PollardRhoParameters {
+        trial_bound: BigUint::from(FACTORIZE_TRIAL_BOUND),
+        rho_rounds: 2048,
+        miller_rabin_rounds: 64,
+    };
+
+    pollard_rho_factorisation(rng, params, n.clone()).unwrap_or_else(|| trial_factorization(n))
+}
+
 #[cfg(test)]
 mod tests {
-    use num::{bigint::RandBigInt, BigUint, Integer};
+    use num::{bigint::RandBigInt, BigUint, Integer, One, Zero};
     use rand::SeedableRng;
 
     use crate::factorization::{
-        pollard_rho_factorisation, pollard_rho_single_factor, trial_factorization,
+        factorize, factorize_deterministic, is_smooth, pollard_rho_custom,
+        pollard_rho_factorisation, pollard_rho_single_factor, trial_divide_up_to,
+        trial_factorization, trial_factorization_with_primes, FactorStream,
     };
+    use crate::primes::{miller_rabin_with_randomness, sieve};
+
+    use super::{Factorization, PollardRhoParameters};
+
+    #[test]
+    fn test_factorize() {
+        let fact = factorize(BigUint::from(360u32));
+        assert_eq!(fact.n(), BigUint::from(360u32));
+        assert_eq!(BigUint::from(&fact), BigUint::from(360u32));
+        assert_eq!(fact.get(&BigUint::from(2u8)), 3);
+
+        assert_eq!(
+            factorize(BigUint::one()),
+            Factorization::new(std::iter::empty())
+        );
+
+        // Large enough to go through rho
+        for p in [65537u64, 1000003, 2147483647].iter() {
+            let fact = factorize(BigUint::from(*p));
+            assert_eq!(fact, Factorization::new(vec![(BigUint::from(*p), 1)]));
+        }
+
+        let n = BigUint::from(1000003u64 * 1000033 * 1000033) * 360u32;
+        let fact = factorize(n.clone());
+        assert_eq!(BigUint::from(&fact), n);
+        assert_eq!(fact.get(&BigUint::from(1000033u64)), 2);
+        assert_eq!(fact, trial_factorization(n));
+    }
 
-    use super::PollardRhoParameters;
+    #[test]
+    fn test_factorize_deterministic() {
+        let composites = [
+            1u64,
+            360,
+            65537 * 65539,
+            1000003 * 1000033 * 1000033,
+            (1 << 61) - 3,
+            1000000007 * 998244353,
+        ];
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in composites.iter() {
+            let n = BigUint::from(*n);
+            let fact = factorize_deterministic(n.clone());
+            assert_eq!(fact.n(), n);
+            assert_eq!(fact, factorize_deterministic(n.clone()));
+            for (p, _) in fact.iter() {
+                assert!(miller_rabin_with_randomness(&mut rng, p.clone(), 64).is_prime());
+            }
+        }
+    }
 
     #[test]
     fn test_trial_factorization() {
@@ -213,6 +599,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_pollard_rho_custom() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let n = BigUint::from(1000003u64 * 1000033);
+        for (c, x0) in [(1u8, 2u8), (3, 5), (7, 0)].iter() {
+            let factor = pollard_rho_custom(
+                &mut rng,
+                &n,
+                &BigUint::from(*c),
+                &BigUint::from(*x0),
+                100000,
+            )
+            .unwrap();
+            assert!(factor == BigUint::from(1000003u32) || factor == BigUint::from(1000033u32));
+        }
+
+        // 8051 = 83 * 97, the textbook example with x^2 + 1 from 2
+        let factor = pollard_rho_custom(
+            &mut rng,
+            &BigUint::from(8051u32),
+            &BigUint::one(),
+            &BigUint::from(2u8),
+            3,
+        );
+        assert_eq!(factor, Some(BigUint::from(97u8)));
+
+        // Too few steps for rho to get anywhere
+        assert_eq!(
+            pollard_rho_custom(&mut rng, &n, &BigUint::one(), &BigUint::from(2u8), 0),
+            None
+        );
+        assert_eq!(
+            pollard_rho_custom(&mut rng, &n, &BigUint::one(), &BigUint::from(2u8), 10),
+            None
+        );
+
+        // Primes have no factor to find, so only the cap stops the search
+        assert_eq!(
+            pollard_rho_custom(
+                &mut rng,
+                &BigUint::from(1000003u32),
+                &BigUint::one(),
+                &BigUint::from(2u8),
+                10000
+            ),
+            None
+        );
+    }
+
     #[test]
     fn test_pollard_rho_factorisation() {
         let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
@@ -232,4 +667,286 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_factor_stream() {
+        let rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let n = BigUint::from(360u32);
+        let params = PollardRhoParameters::default_for(&n);
+        let mut factors: Vec<_> = FactorStream::new(rng.clone(), params, n.clone()).collect();
+        assert_eq!(factors.iter().product::<BigUint>(), n);
+        factors.sort();
+        let expected: Vec<_> = [2u8, 2, 2, 3, 3, 5]
+            .iter()
+            .map(|p| BigUint::from(*p))
+            .collect();
+        assert_eq!(factors, expected);
+
+        let p = BigUint::from(1000003u32);
+        let params = PollardRhoParameters::default_for(&p);
+        let factors: Vec<_> = FactorStream::new(rng.clone(), params, p.clone()).collect();
+        assert_eq!(factors, vec![p]);
+
+        let n = BigUint::from(1000003u64 * 1000033 * 1000033) * 360u32;
+        let params = PollardRhoParameters::default_for(&n);
+        let mut stream = FactorStream::new(rng.clone(), params, n.clone());
+        let mut seen = BigUint::one();
+        while let Some(p) = stream.next() {
+            seen *= p;
+            assert_eq!(seen.clone() * stream.remaining(), n);
+        }
+        assert!(stream.remaining().is_one());
+        assert_eq!(seen, n);
+
+        let params = PollardRhoParameters::for_bits(1);
+        assert_eq!(FactorStream::new(rng, params, BigUint::one()).count(), 0);
+    }
+
+    #[test]
+    fn test_pollard_rho_parameters() {
+        let params: Vec<_> = (0..256).map(PollardRhoParameters::for_bits).collect();
+        for (smaller, larger) in params.iter().zip(params.iter().skip(1)) {
+            assert!(smaller.rho_rounds < larger.rho_rounds);
+            assert!(smaller.trial_bound <= larger.trial_bound);
+        }
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let composites = [
+            360u64,
+            65537 * 65539,
+            1000003 * 1000033 * 1000033,
+            1000000007 * 998244353,
+            (1 << 61) - 3,
+        ];
+        for n in composites.iter() {
+            let n = BigUint::from(*n);
+            let params = PollardRhoParameters::default_for(&n);
+            let fact = pollard_rho_factorisation(&mut rng, params, n.clone()).unwrap();
+            assert_eq!(fact.n(), n);
+            for (p, _) in fact.iter() {
+                assert!(miller_rabin_with_randomness(&mut rng, p.clone(), 64).is_prime());
+            }
+        }
+    }
+
+    #[test]
+    fn test_pollard_rho_tiny_inputs() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in 0..4u8 {
+            assert_eq!(pollard_rho_single_factor(&mut rng, BigUint::from(n)), None);
+        }
+
+        let params = PollardRhoParameters {
+            trial_bound: BigUint::zero(),
+            rho_rounds: 16,
+            miller_rabin_rounds: 16,
+        };
+        for n in 2..50u32 {
+            let n = BigUint::from(n);
+            let fact = pollard_rho_factorisation(&mut rng, params.clone(), n.clone()).unwrap();
+            assert_eq!(fact.n(), n);
+            assert_eq!(fact, trial_factorization(n));
+        }
+    }
+
+    #[test]
+    fn test_valuation() {
+        let fact = trial_factorization(BigUint::from(360u32));
+        assert_eq!(fact.valuation(&BigUint::from(2u8)), 3);
+        assert_eq!(fact.valuation(&BigUint::from(3u8)), 2);
+        assert_eq!(fact.valuation(&BigUint::from(5u8)), 1);
+        assert_eq!(fact.valuation(&BigUint::from(7u8)), 0);
+    }
+
+    #[test]
+    fn test_quotient_by() {
+        let fact = trial_factorization(BigUint::from(360u32));
+        let expected = Factorization::new(vec![
+            (BigUint::from(2u8), 1),
+            (BigUint::from(3u8), 2),
+            (BigUint::from(5u8), 1),
+        ]);
+        assert_eq!(fact.quotient_by(&BigUint::from(2u8), 2), expected);
+        assert_eq!(expected.n(), BigUint::from(90u8));
+
+        // Removing more copies than present drops the prime entirely
+        let no_twos = fact.quotient_by(&BigUint::from(2u8), 10);
+        assert_eq!(no_twos.valuation(&BigUint::from(2u8)), 0);
+        assert_eq!(no_twos.n(), BigUint::from(45u8));
+
+        assert_eq!(fact.quotient_by(&BigUint::from(7u8), 1), fact);
+    }
+
+    #[test]
+    fn test_carmichael_lambda() {
+        let lambda = |n: u32| trial_factorization(BigUint::from(n)).carmichael_lambda();
+        assert_eq!(lambda(1), BigUint::one());
+        assert_eq!(lambda(2), BigUint::one());
+        assert_eq!(lambda(4), BigUint::from(2u8));
+        assert_eq!(lambda(8), BigUint::from(2u8));
+        assert_eq!(lambda(15), BigUint::from(4u8));
+        assert_eq!(lambda(360), BigUint::from(12u8));
+        assert_eq!(lambda(561), BigUint::from(80u8));
+    }
+
+    #[test]
+    fn test_carmichael_lambda_is_universal_exponent() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in 2..500u32 {
+            let n = BigUint::from(n);
+            let lambda = trial_factorization(n.clone()).carmichael_lambda();
+            for _ in 0..10 {
+                let a = rng.gen_biguint_range(&BigUint::one(), &n);
+                if a.gcd(&n).is_one() {
+                    assert!(a.modpow(&lambda, &n).is_one());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_smooth() {
+        let five = BigUint::from(5u8);
+        assert!(is_smooth(&BigUint::from(720u32), &five));
+        assert!(is_smooth(&BigUint::from(1u32), &five));
+        assert!(!is_smooth(&BigUint::from(720u32 * 7), &five));
+        assert!(!is_smooth(
+            &BigUint::from(2u32 * 1000003),
+            &BigUint::from(1000u32)
+        ));
+        assert!(is_smooth(
+            &BigUint::from(2u32 * 1000003),
+            &BigUint::from(1000003u32)
+        ));
+    }
+
+    #[test]
+    fn test_trial_divide_up_to() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let bound = BigUint::from(100u8);
+
+        // 2^4 * 3^2 * 5 * 1000003
+        let n = BigUint::from(720u32) * 1000003u32;
+        let (smooth, cofactor) = trial_divide_up_to(n, &bound);
+        assert_eq!(smooth.n(), BigUint::from(720u32));
+        assert_eq!(cofactor, BigUint::from(1000003u32));
+
+        const ROUNDS: usize = 1000;
+        for _ in 0..ROUNDS {
+            let num = rng.gen_biguint_range(&BigUint::from(1u8), &BigUint::from(1000000usize));
+            let (smooth, cofactor) = trial_divide_up_to(num.clone(), &bound);
+            assert_eq!(smooth.n() * cofactor.clone(), num);
+            assert!(is_smooth(&smooth.n(), &bound));
+            for p in 2..=100u32 {
+                assert!(!cofactor.is_multiple_of(&BigUint::from(p)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_iteration() {
+        let fact = trial_factorization(BigUint::from(360u32));
+        let expected = vec![
+            (BigUint::from(2u8), 3),
+            (BigUint::from(3u8), 2),
+            (BigUint::from(5u8), 1),
+        ];
+
+        let borrowed: Vec<_> = fact.iter().map(|(p, e)| (p.clone(), e)).collect();
+        assert_eq!(borrowed, expected);
+
+        let owned: Vec<_> = fact.clone().into_iter().collect();
+        assert_eq!(owned, expected);
+
+        assert_eq!(Factorization::new(owned), fact);
+        assert_eq!(trial_factorization(BigUint::from(1u8)).iter().count(), 0);
+    }
+
+    #[test]
+    fn test_squarefree_part_and_power() {
+        let fact = trial_factorization(BigUint::from(72u32));
+        let radical = fact.squarefree_part();
+        let six = Factorization::new(vec![(BigUint::from(2u8), 1), (BigUint::from(3u8), 1)]);
+        assert_eq!(radical, six);
+        assert_eq!(radical.n(), BigUint::from(6u8));
+        assert_eq!(radical.mobius(), 1);
+
+        assert_eq!(
+            six.power(2),
+            Factorization::new(vec![(BigUint::from(2u8), 2), (BigUint::from(3u8), 2)])
+        );
+        assert_eq!(fact.power(3).n(), BigUint::from(72u32).pow(3));
+        assert_eq!(fact.power(0).n(), BigUint::one());
+        assert_eq!(fact.power(1), fact);
+    }
+
+    #[test]
+    fn test_get() {
+        let fact = trial_factorization(BigUint::from(360u32));
+        assert_eq!(fact.get(&BigUint::from(2u8)), 3);
+        assert_eq!(fact.get(&BigUint::from(3u8)), 2);
+        assert_eq!(fact.get(&BigUint::from(5u8)), 1);
+        assert_eq!(fact.get(&BigUint::from(7u8)), 0);
+        assert_eq!(fact.get(&BigUint::from(4u8)), 0);
+    }
+
+    // The straightforward version, trying every integer in turn
+    fn naive_trial_factorization(mut n: BigUint) -> Factorization {
+        let mut res = Vec::new();
+        let mut s = BigUint::from(2u8);
+        while n != BigUint::one() {
+            if n.is_multiple_of(&s) {
+                let mut mult = 0;
+                while n.is_multiple_of(&s) {
+                    n /= &s;
+                    mult += 1;
+                }
+                res.push((s.clone(), mult));
+            }
+            s += 1u8;
+        }
+        Factorization::new(res)
+    }
+
+    #[test]
+    fn test_trial_factorization_matches_naive() {
+        for i in 1..1000u32 {
+            let n = BigUint::from(i);
+            assert_eq!(trial_factorization(n.clone()), naive_trial_factorization(n));
+        }
+
+        // Factors beyond the sieve
+        let n = BigUint::from(4099u64 * 4099 * 8191 * 6);
+        assert_eq!(trial_factorization(n.clone()), naive_trial_factorization(n));
+    }
+
+    #[test]
+    fn test_trial_factorization_with_primes() {
+        let primes = sieve(100);
+        for i in 1..1000u32 {
+            let n = BigUint::from(i);
+            let (fact, cofactor) = trial_factorization_with_primes(n.clone(), &primes);
+            assert_eq!(fact.n() * cofactor.clone(), n);
+            if cofactor.is_one() {
+                assert_eq!(fact, trial_factorization(n));
+            }
+        }
+
+        // 2 * 3 * 1000003
+        let n = BigUint::from(6u8) * 1000003u32;
+        let (fact, cofactor) = trial_factorization_with_primes(n, &primes);
+        assert_eq!(fact.n(), BigUint::from(6u8));
+        assert_eq!(cofactor, BigUint::from(1000003u32));
+
+        let (fact, cofactor) = trial_factorization_with_primes(BigUint::from(1000003u32), &[]);
+        assert_eq!(fact.n(), BigUint::one());
+        assert_eq!(cofactor, BigUint::from(1000003u32));
+
+        // 1000003 < 1009^2, so division stops at 1009 and records it as a prime
+        let large_primes = sieve(1100);
+        let (fact, cofactor) =
+            trial_factorization_with_primes(BigUint::from(1000003u32), &large_primes);
+        assert_eq!(fact, naive_trial_factorization(BigUint::from(1000003u32)));
+        assert!(cofactor.is_one());
+    }
 }
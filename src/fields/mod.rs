@@ -1,6 +1,10 @@
 pub mod primefields;
 
-use num::{BigUint, Integer};
+use std::any::Any;
+use std::fmt;
+
+use num::{BigInt, BigUint, Integer, One, ToPrimitive};
+use rand::RngCore;
 
 // This is actually need for the macro for tests to compile
 #[allow(unused_imports)]
@@ -8,6 +12,8 @@ use paste::paste;
 
 use crate::{
     double_and_add::{possibly_negative_double_and_add, PossiblyNegativeDoubleAndAddState},
+    factorization::Factorization,
+    primes::rewrite_n,
     rings::Ring,
 };
 
@@ -16,6 +22,35 @@ pub trait Field: Ring {
 
     fn characteristic() -> BigUint;
 
+    // Degree over the prime subfield
+    fn extension_degree() -> u32 {
+        1
+    }
+
+    // Number of elements, p^n
+    fn order() -> BigUint {
+        Self::characteristic().pow(Self::extension_degree())
+    }
+
+    // Every non zero element generates a copy of Z_p under addition
+    fn additive_order(&self) -> BigUint {
+        if self.is_zero() {
+            BigUint::one()
+        } else {
+            Self::characteristic()
+        }
+    }
+
+    fn from_i64(x: i64) -> Self {
+        Self::from_bigint(&BigInt::from(x))
+    }
+
+    // Reduces modulo the characteristic first, so the embedding stays cheap
+    fn from_bigint(x: &BigInt) -> Self {
+        let characteristic = BigInt::from(Self::characteristic());
+        Self::integer_embed(x.mod_floor(&characteristic))
+    }
+
     fn pow(&self, i: impl Integer) -> Self {
         if self.is_zero() {
             return Self::zero();
@@ -30,6 +65,351 @@ pub trait Field: Ring {
 
         possibly_negative_double_and_add(state, i)
     }
+
+    fn pow_biguint(&self, exponent: &BigUint) -> Self {
+        self.positive_pow_biguint(exponent)
+    }
+
+    // Euler's criterion, 1 for non zero squares, -1 for non squares and 0 for zero
+    // Assumes the field has odd order
+    fn legendre(&self) -> i8 {
+        if self.is_zero() {
+            return 0;
+        }
+
+        if self.pow_biguint(&((Self::order() - 1u8) / 2u8)).is_one() {
+            1
+        } else {
+            -1
+        }
+    }
+
+    // Tonelli-Shanks, assumes the field has odd order
+    // The non residue is searched for in the prime subfield
+    fn sqrt(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::zero());
+        }
+
+        if self.legendre() != 1 {
+            return None;
+        }
+
+        // order = 2^s t + 1
+        let (s, t) = rewrite_n(Self::order());
+
+        let mut z = Self::one();
+        loop {
+            z += Self::one();
+            if z.is_zero() {
+                panic!("No non residue in the prime subfield");
+            }
+            if z.legendre() == -1 {
+                break;
+            }
+        }
+
+        let mut m = s;
+        let mut c = z.pow_biguint(&t);
+        let mut u = self.pow_biguint(&t);
+        let mut r = self.pow_biguint(&((t + 1u8) / 2u8));
+
+        while !u.is_one() {
+            // Least i with u^(2^i) = 1, necessarily i < m
+            let mut i = 0;
+            let mut u_pow = u.clone();
+            while !u_pow.is_one() {
+                u_pow = u_pow.square();
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b.square();
+            }
+
+            m = i;
+            c = b.square();
+            u *= c.clone();
+            r *= b;
+        }
+
+        Some(r)
+    }
+
+    // Cubing is a bijection unless 3 divides order - 1, in which case we use
+    // the cube analogue of Tonelli-Shanks. Again the non cube is searched for in the prime subfield
+    fn cube_root(&self) -> Option<Self> {
+        if self.is_zero() {
+            return Some(Self::zero());
+        }
+
+        let order = Self::order();
+        let three = BigUint::from(3u8);
+        match (&order % &three).to_u8() {
+            // Frobenius
+            Some(0) => return Some(self.pow_biguint(&(order / three))),
+            // x^(2q - 1) = x
+            Some(2) => return Some(self.pow_biguint(&((order * 2u8 - 1u8) / three))),
+            _ => {}
+        }
+
+        if !self.pow_biguint(&((&order - 1u8) / &three)).is_one() {
+            return None;
+        }
+
+        // order = 3^s t + 1
+        let mut s = 0;
+        let mut t = &order - 1u8;
+        while t.is_multiple_of(&three) {
+            t /= &three;
+            s += 1;
+        }
+
+        let mut z = Self::one();
+        loop {
+            z += Self::one();
+            if z.is_zero() {
+                panic!("No non cube in the prime subfield");
+            }
+            if !z.pow_biguint(&((&order - 1u8) / &three)).is_one() {
+                break;
+            }
+        }
+
+        // 3e = 1 mod t, so r^3 / x below lies in the 3-Sylow subgroup, generated by c
+        let e = if (&t % &three).is_one() {
+            (&t * 2u8 + 1u8) / &three
+        } else {
+            (&t + 1u8) / &three
+        };
+        let r = self.pow_biguint(&e);
+        let b = r.square() * &r * self.invert().unwrap();
+        let c = z.pow_biguint(&t);
+
+        // Discrete log of b in base c, one base 3 digit at a time
+        let omega = c.pow_biguint(&three.pow(s - 1));
+        let mut k = BigUint::from(0u8);
+        let c_inv = c.invert().unwrap();
+        for i in 0..s {
+            let residual = c_inv.pow_biguint(&k) * &b;
+            let digit = residual.pow_biguint(&three.pow(s - 1 - i));
+            if digit == omega {
+                k += three.pow(i);
+            } else if digit == omega.square() {
+                k += three.pow(i) * 2u8;
+            }
+        }
+
+        // b is a cube, so 3 divides k, and (r / c^(k / 3))^3 = r^3 / b = x
+        debug_assert!(k.is_multiple_of(&three));
+        Some(r * c_inv.pow_biguint(&(k / three)))
+    }
+}
+
+// Fields Z/p, whose elements have a canonical integer representative in [0, p)
+pub trait PrimeField: Field {
+    // The canonical representative, in [0, p)
+    fn as_biguint(&self) -> &BigUint;
+
+    fn to_biguint(&self) -> BigUint {
+        self.as_biguint().clone()
+    }
+
+    // Bit and byte length of p
+    fn modulus_bits() -> u64;
+    fn modulus_bytes() -> usize {
+        Self::modulus_bits().div_ceil(8) as usize
+    }
+
+    // Little endian, always padded to modulus_bytes
+    fn to_bytes_le(&self) -> Vec<u8> {
+        let mut bytes = self.as_biguint().to_bytes_le();
+        bytes.resize(Self::modulus_bytes(), 0);
+        bytes
+    }
+
+    // Inverse of to_bytes_le, None unless there are exactly modulus_bytes bytes encoding a reduced element
+    fn from_bytes_le(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != Self::modulus_bytes() {
+            return None;
+        }
+        let el = BigUint::from_bytes_le(bytes);
+        if el >= Self::characteristic() {
+            return None;
+        }
+        Some(Self::from_bigint(&BigInt::from(el)))
+    }
+
+    // Samples an integer uniformly in [0, bound) and reduces it mod p
+    fn random_below(rng: &mut impl RngCore, bound: &BigUint) -> Self;
+
+    // Samples an integer uniformly in [0, 2^bits) and reduces it mod p
+    fn random_bits(rng: &mut impl RngCore, bits: u64) -> Self;
+
+    // Factorization of p - 1, computed once per field
+    fn multiplicative_group_factorization() -> &'static Factorization;
+
+    // The smallest generator of the multiplicative group
+    fn multiplicative_generator() -> Self {
+        let n = Self::order() - 1u8;
+        let mut g = Self::one();
+        loop {
+            if Self::multiplicative_group_factorization()
+                .iter()
+                .all(|(q, _)| !g.pow_biguint(&(n.clone() / q)).is_one())
+            {
+                return g;
+            }
+            g += Self::one();
+        }
+    }
+
+    // Order of a non zero element in the multiplicative group
+    fn multiplicative_order(&self) -> BigUint {
+        if self.is_zero() {
+            panic!("Zero has no multiplicative order");
+        }
+
+        let mut order = Self::order() - 1u8;
+        for (q, e) in Self::multiplicative_group_factorization().iter() {
+            for _ in 0..e {
+                let candidate = order.clone() / q;
+                if !self.pow_biguint(&candidate).is_one() {
+                    break;
+                }
+                order = candidate;
+            }
+        }
+        order
+    }
+}
+
+// Object safe view of field elements, so that they can live in a Vec<Box<dyn FieldOps>>
+// Constructors like zero and one are not available, and mixing elements of different fields panics
+pub trait FieldOps: fmt::Debug {
+    fn as_any(&self) -> &dyn Any;
+    fn clone_box(&self) -> Box<dyn FieldOps>;
+    fn is_zero_dyn(&self) -> bool;
+    fn eq_dyn(&self, other: &dyn FieldOps) -> bool;
+    fn neg_dyn(&self) -> Box<dyn FieldOps>;
+    fn add_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps>;
+    fn sub_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps>;
+    fn mul_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps>;
+    fn invert_dyn(&self) -> Option<Box<dyn FieldOps>>;
+}
+
+fn downcast<F: 'static>(el: &dyn FieldOps) -> &F {
+    el.as_any()
+        .downcast_ref()
+        .expect("Field elements are from different fields")
+}
+
+impl<F: Field + fmt::Debug + 'static> FieldOps for F {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone_box(&self) -> Box<dyn FieldOps> {
+        Box::new(self.clone())
+    }
+
+    fn is_zero_dyn(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn eq_dyn(&self, other: &dyn FieldOps) -> bool {
+        other.as_any().downcast_ref::<F>() == Some(self)
+    }
+
+    fn neg_dyn(&self) -> Box<dyn FieldOps> {
+        Box::new(-self.clone())
+    }
+
+    fn add_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps> {
+        Box::new(self.clone() + downcast::<F>(other))
+    }
+
+    fn sub_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps> {
+        Box::new(self.clone() - downcast::<F>(other))
+    }
+
+    fn mul_dyn(&self, other: &dyn FieldOps) -> Box<dyn FieldOps> {
+        Box::new(self.clone() * downcast::<F>(other))
+    }
+
+    fn invert_dyn(&self) -> Option<Box<dyn FieldOps>> {
+        self.invert().map(|inv| Box::new(inv) as Box<dyn FieldOps>)
+    }
+}
+
+impl Clone for Box<dyn FieldOps> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+// Fixed base exponentiation with a precomputed table, table[i][j] = base^(j 2^(window i))
+// Exponents are reduced modulo the order of the multiplicative group,
+// so the table only needs to cover the bit length of the field order
+#[derive(Debug, Clone)]
+pub struct FixedBaseExp<F: Field> {
+    base: F,
+    window: u64,
+    table: Vec<Vec<F>>,
+}
+
+impl<F: Field> FixedBaseExp<F> {
+    pub fn new(base: F, window: u64) -> Self {
+        if window == 0 || window > 16 {
+            panic!("Window size must be between 1 and 16");
+        }
+
+        let windows = F::order().bits().div_ceil(window);
+        let mut table = Vec::with_capacity(windows as usize);
+        let mut window_base = base.clone();
+        for _ in 0..windows {
+            let mut row = Vec::with_capacity(1 << window);
+            row.push(F::one());
+            for j in 1..(1 << window) {
+                let next = row[j - 1].clone() * &window_base;
+                row.push(next);
+            }
+            window_base = row[row.len() - 1].clone() * &window_base;
+            table.push(row);
+        }
+
+        FixedBaseExp {
+            base,
+            window,
+            table,
+        }
+    }
+
+    pub fn base(&self) -> &F {
+        &self.base
+    }
+
+    pub fn pow(&self, exponent: &BigUint) -> F {
+        if self.base.is_zero() {
+            return self.base.pow_biguint(exponent);
+        }
+
+        let exponent = exponent % (F::order() - 1u8);
+
+        let mut acc = F::one();
+        for (i, row) in self.table.iter().enumerate() {
+            let mut digit = 0;
+            for b in (0..self.window).rev() {
+                digit = (digit << 1) | exponent.bit(i as u64 * self.window + b) as u64;
+            }
+            let digit = digit as usize;
+            if digit != 0 {
+                acc *= row[digit].clone();
+            }
+        }
+        acc
+    }
 }
 
 #[macro_export]
@@ -42,6 +422,7 @@ macro_rules! field_tests {
                 use crate::fields::Field;
                 use crate::rings::Ring;
                 use crate::primes::miller_rabin_with_randomness;
+                use num::bigint::RandBigInt;
                 use rand::SeedableRng;
 
                 #[test]
@@ -61,6 +442,51 @@ macro_rules! field_tests {
                     );
                 }
 
+                #[test]
+                fn order_is_char_power() {
+                    assert!($ff::extension_degree() >= 1);
+                    assert_eq!(
+                        $ff::order(),
+                        $ff::characteristic().pow($ff::extension_degree())
+                    );
+                }
+
+                #[test]
+                fn pow_biguint() {
+                    const NUM_ELEMENTS: usize = 100;
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random(&mut rng);
+                        for i in 0..64u32 {
+                            let exp = num::BigUint::from(i);
+                            assert_eq!(el.pow_biguint(&exp), el.pow(i));
+                        }
+
+                        let exp = rng.gen_biguint(512);
+                        assert_eq!(el.pow_biguint(&exp), el.pow(exp.clone()));
+                    }
+                }
+
+                #[test]
+                fn square_roots() {
+                    const NUM_ELEMENTS: usize = 1000;
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    assert_eq!($ff::zero().sqrt(), Some($ff::zero()));
+                    assert_eq!($ff::zero().legendre(), 0);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random_non_zero(&mut rng);
+                        let square = el.square();
+                        assert_eq!(square.legendre(), 1);
+                        let root = square.sqrt().unwrap();
+                        assert!(root == el || root == -el.clone());
+
+                        match el.sqrt() {
+                            Some(root) => assert_eq!(root.square(), el),
+                            None => assert_eq!(el.legendre(), -1),
+                        }
+                    }
+                }
+
                 #[test]
                 fn multiplicative_identies_with_inversions() {
                     const NUM_ELEMENTS: usize = 1000;
@@ -77,3 +503,138 @@ macro_rules! field_tests {
         }
     };
 }
+
+// Consistency of the derived operations against their naive definitions
+#[macro_export]
+macro_rules! field_extended_tests {
+    ($ff:ident) => {
+        paste! {
+            #[cfg(test)]
+            mod [< $ff:snake _field_extended_tests >] {
+                use super::$ff;
+                use crate::fields::Field;
+                use crate::rings::Ring;
+                use rand::SeedableRng;
+
+                const NUM_ELEMENTS: usize = 100;
+                const SMALL_EXPONENTS: i64 = 32;
+
+                #[test]
+                fn pow_is_repeated_multiplication() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random_non_zero(&mut rng);
+                        let inv = el.invert().unwrap();
+                        let mut acc = $ff::one();
+                        let mut inv_acc = $ff::one();
+                        for k in 0..SMALL_EXPONENTS {
+                            assert_eq!(el.pow(k), acc);
+                            assert_eq!(el.pow(-k), inv_acc);
+                            acc *= &el;
+                            inv_acc *= &inv;
+                        }
+                    }
+                }
+
+                #[test]
+                fn scale_is_repeated_addition() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random(&mut rng);
+                        let mut acc = $ff::zero();
+                        for k in 0..SMALL_EXPONENTS {
+                            assert_eq!(el.scale(k), acc);
+                            assert_eq!(el.scale(-k), -acc.clone());
+                            acc += &el;
+                        }
+                    }
+                }
+
+                #[test]
+                fn square_is_self_product() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random(&mut rng);
+                        assert_eq!(el.square(), el.clone() * el.clone());
+                    }
+                }
+
+                #[test]
+                fn double_inversion() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random_non_zero(&mut rng);
+                        assert_eq!(el.invert().unwrap().invert().unwrap(), el);
+                    }
+                }
+
+                #[test]
+                fn fermat() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    assert!($ff::zero().pow($ff::order()).is_zero());
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $ff::random(&mut rng);
+                        assert_eq!(el.pow($ff::order()), el);
+                    }
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::primefields::PrimeField4999;
+
+    #[test]
+    fn fixed_base_exp() {
+        // 3 generates the multiplicative group mod 4999
+        let generator = PrimeField4999::new(BigUint::from(3u8));
+        for window in 1..=6 {
+            let table = FixedBaseExp::new(generator.clone(), window);
+            for k in (0u32..5000).chain([9998, 10000, 123456789].iter().copied()) {
+                let k = BigUint::from(k);
+                assert_eq!(table.pow(&k), generator.pow_biguint(&k));
+            }
+        }
+    }
+
+    #[test]
+    fn field_ops_trait_objects() {
+        let el = |x: u32| Box::new(PrimeField4999::new(BigUint::from(x))) as Box<dyn FieldOps>;
+        let elements = [el(0), el(1), el(2), el(4998)];
+
+        let sum = elements
+            .iter()
+            .fold(el(0), |acc, x| acc.add_dyn(x.as_ref()));
+        assert!(sum.eq_dyn(el(2).as_ref()));
+
+        let product = elements[1..]
+            .iter()
+            .fold(el(1), |acc, x| acc.mul_dyn(x.as_ref()));
+        assert!(product.eq_dyn(el(4997).as_ref()));
+
+        let difference = elements[1].sub_dyn(elements[2].as_ref());
+        assert!(difference.eq_dyn(elements[3].as_ref()));
+        assert!(elements[3].neg_dyn().eq_dyn(elements[1].clone().as_ref()));
+        assert!(!elements[3].eq_dyn(elements[1].as_ref()));
+
+        assert!(elements[0].is_zero_dyn());
+        assert!(elements[0].invert_dyn().is_none());
+        let inverse = elements[2].invert_dyn().unwrap();
+        assert!(inverse.mul_dyn(elements[2].as_ref()).eq_dyn(el(1).as_ref()));
+
+        // Back to the concrete type
+        assert_eq!(
+            elements[2].as_any().downcast_ref::<PrimeField4999>(),
+            Some(&PrimeField4999::new(BigUint::from(2u8)))
+        );
+    }
+
+    #[test]
+    fn fixed_base_exp_zero_base() {
+        let table = FixedBaseExp::new(PrimeField4999::zero(), 4);
+        assert_eq!(table.pow(&BigUint::from(5u8)), PrimeField4999::zero());
+    }
+}
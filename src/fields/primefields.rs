@@ -1,20 +1,37 @@
-use super::Field;
-use crate::field_tests;
+use super::{Field, PrimeField};
+use crate::{field_extended_tests, field_tests};
 use lazy_static::lazy_static;
 use num::bigint::RandBigInt;
 use num::{BigUint, One, Zero};
 use paste::paste;
 use rand::RngCore;
 use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
 // MAKE SURE TO CALL THIS WITH A PRIME NUMBER!
 #[macro_export]
 macro_rules! field_generate {
     ($ff:ident, $mod:expr) => {
+        crate::field_generate!($ff, $mod, remainder);
+    };
+    ($ff:ident, $mod:expr, $reduction:ident) => {
         paste! {
 
-        crate::ring_generate!($ff, $mod);
+        crate::ring_generate!($ff, $mod, $reduction);
+
+        // Counts how many times the factorization below is computed
+        #[cfg(test)]
+        static [<$ff:upper _FACTORIZATION_CALLS>]: std::sync::atomic::AtomicUsize =
+            std::sync::atomic::AtomicUsize::new(0);
+
+        lazy_static! {
+            // Factorization of p - 1, the order of the multiplicative group
+            static ref [<$ff:upper _ORDER_FACTORIZATION>]: crate::factorization::Factorization = {
+                #[cfg(test)]
+                [<$ff:upper _FACTORIZATION_CALLS>].fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                crate::factorization::factorize(&*[<$ff:upper _MODULO>] - BigUint::one())
+            };
+        }
 
         impl Field for $ff {
             fn invert(&self) -> Option<Self> {
@@ -41,10 +58,419 @@ macro_rules! field_generate {
                 return [<$ff:upper _MODULO>].clone();
             }
         }
+
+        impl PrimeField for $ff {
+            fn as_biguint(&self) -> &BigUint {
+                &self.el
+            }
+
+            fn modulus_bits() -> u64 {
+                [<$ff:upper _MODULO>].bits()
+            }
+
+            fn random_below(rng: &mut impl RngCore, bound: &BigUint) -> Self {
+                Self::new(rng.gen_biguint_below(bound))
+            }
+
+            fn random_bits(rng: &mut impl RngCore, bits: u64) -> Self {
+                Self::new(rng.gen_biguint(bits))
+            }
+
+            fn multiplicative_group_factorization() -> &'static crate::factorization::Factorization {
+                &*[<$ff:upper _ORDER_FACTORIZATION>]
+            }
+        }
                 }
 
         field_tests!($ff);
     };
 }
 
+// field_generate_many!((F7, BigUint::from(7u8)), (F11, BigUint::from(11u8)))
+#[macro_export]
+macro_rules! field_generate_many {
+    ($(($ff:ident, $mod:expr)),+ $(,)?) => {
+        $(crate::field_generate!($ff, $mod);)+
+    };
+}
+
 field_generate!(PrimeField4999, BigUint::from(4999u32));
+field_extended_tests!(PrimeField4999);
+
+// p - 1 = 2^16, so NTT domains up to size 2^16 exist
+field_generate!(NttField65537, BigUint::from(65537u32));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rings::Ring;
+    use num::ToPrimitive;
+    use rand::SeedableRng;
+
+    // p - 1 = 2^16, exercising every step of Tonelli-Shanks
+    field_generate!(PrimeField65537, BigUint::from(65537u32));
+
+    // 101 = 2 mod 3, and 109 - 1 = 4 * 27, for cube roots
+    field_generate!(PrimeField101, BigUint::from(101u8));
+    field_generate!(PrimeField109, BigUint::from(109u8));
+
+    field_generate!(BarrettPrimeField4999, BigUint::from(4999u32), barrett);
+    field_generate!(PrimeField2e127m1, BigUint::from(u128::MAX >> 1));
+    field_generate!(
+        BarrettPrimeField2e127m1,
+        BigUint::from(u128::MAX >> 1),
+        barrett
+    );
+
+    #[test]
+    fn order_and_characteristic() {
+        assert_eq!(PrimeField4999::characteristic(), BigUint::from(4999u32));
+        assert_eq!(PrimeField4999::order(), BigUint::from(4999u32));
+        assert_eq!(PrimeField4999::extension_degree(), 1);
+    }
+
+    #[test]
+    fn random_below() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let bound = BigUint::from(10u8);
+        let mut seen = [false; 10];
+        for _ in 0..1000 {
+            let el = PrimeField4999::random_below(&mut rng, &bound).to_biguint();
+            assert!(el < bound);
+            seen[el.to_usize().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+
+        // Larger than the modulus, so we see the reduction
+        let bound = BigUint::from(4999u32 * 1000);
+        for _ in 0..1000 {
+            let el = PrimeField4999::random_below(&mut rng, &bound);
+            assert!(el.to_biguint() < PrimeField4999::characteristic());
+        }
+    }
+
+    #[test]
+    fn random_bits() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let mut seen = [false; 16];
+        for _ in 0..1000 {
+            let el = PrimeField4999::random_bits(&mut rng, 4).to_biguint();
+            assert!(el < BigUint::from(16u8));
+            seen[el.to_usize().unwrap()] = true;
+        }
+        assert!(seen.iter().all(|s| *s));
+        assert!(PrimeField4999::random_bits(&mut rng, 0).is_zero());
+    }
+
+    #[test]
+    fn barrett_matches_remainder() {
+        const CHAIN_LENGTH: usize = 10000;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+
+        let mut acc = PrimeField4999::one();
+        let mut barrett_acc = BarrettPrimeField4999::one();
+        for _ in 0..CHAIN_LENGTH {
+            let el = PrimeField4999::random_non_zero(&mut rng);
+            let barrett_el = BarrettPrimeField4999::new(el.to_biguint());
+            acc *= &el;
+            barrett_acc *= &barrett_el;
+            assert_eq!(acc.to_biguint(), barrett_acc.to_biguint());
+        }
+
+        let mut acc = PrimeField2e127m1::one();
+        let mut barrett_acc = BarrettPrimeField2e127m1::one();
+        for _ in 0..CHAIN_LENGTH {
+            let el = PrimeField2e127m1::random_non_zero(&mut rng);
+            let barrett_el = BarrettPrimeField2e127m1::new(el.to_biguint());
+            acc *= el;
+            barrett_acc *= barrett_el;
+            assert_eq!(acc.to_biguint(), barrett_acc.to_biguint());
+        }
+    }
+
+    #[test]
+    fn cube_root() {
+        fn check<F: PrimeField + std::hash::Hash + std::fmt::Debug>() -> usize {
+            let mut cubes = std::collections::HashSet::new();
+            for i in 0..F::characteristic().to_u64().unwrap() {
+                let x = F::from_i64(i as i64);
+                cubes.insert(x.square() * &x);
+            }
+
+            for i in 0..F::characteristic().to_u64().unwrap() {
+                let x = F::from_i64(i as i64);
+                match x.cube_root() {
+                    Some(r) => assert_eq!(r.square() * &r, x),
+                    None => assert!(!cubes.contains(&x)),
+                }
+            }
+            cubes.len()
+        }
+
+        // A third of the units, plus zero
+        assert_eq!(check::<PrimeField4999>(), 4998 / 3 + 1);
+        assert_eq!(check::<PrimeField109>(), 108 / 3 + 1);
+        // Every element is a cube, and the root is unique
+        assert_eq!(check::<PrimeField101>(), 101);
+    }
+
+    #[test]
+    fn sub_ref() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let a = PrimeField4999::random(&mut rng);
+            let b = PrimeField4999::random(&mut rng);
+            assert_eq!(a.sub_ref(&b), a.clone() - &b);
+        }
+
+        // Wrap around
+        let small = PrimeField4999::new(BigUint::from(3u8));
+        let large = PrimeField4999::new(BigUint::from(4000u32));
+        assert_eq!(small.sub_ref(&large).to_biguint(), BigUint::from(1002u32));
+        assert_eq!(small.sub_ref(&large), small.clone() - &large);
+        assert_eq!(small.sub_ref(&small), PrimeField4999::zero());
+    }
+
+    #[test]
+    fn assign_by_reference() {
+        const ROUNDS: usize = 1000;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..ROUNDS {
+            let a = PrimeField4999::random(&mut rng);
+            let b = PrimeField4999::random(&mut rng);
+
+            let mut c = a.clone();
+            c += &b;
+            assert_eq!(c, a.clone() + &b);
+
+            let mut c = a.clone();
+            c *= &b;
+            assert_eq!(c, a.clone() * &b);
+
+            let mut c = a.clone();
+            c -= &b;
+            assert_eq!(c, a.clone() - &b);
+
+            let mut c = a.clone();
+            c -= b.clone();
+            assert_eq!(c, a - b);
+        }
+    }
+
+    #[test]
+    fn checked_constructors() {
+        assert!(PrimeField4999::try_new(BigUint::from(5000u32)).is_none());
+        assert!(PrimeField4999::try_new(BigUint::from(4999u32)).is_none());
+        assert_eq!(
+            PrimeField4999::try_new(BigUint::from(4998u32)),
+            Some(-PrimeField4999::one())
+        );
+        assert_eq!(
+            PrimeField4999::from_canonical(BigUint::from(42u32)),
+            PrimeField4999::new(BigUint::from(42u32))
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic]
+    fn from_canonical_out_of_range() {
+        PrimeField4999::from_canonical(BigUint::from(5000u32));
+    }
+
+    #[test]
+    fn checked_neg() {
+        for i in 0..4999u32 {
+            let el = PrimeField4999::new(BigUint::from(i));
+            assert_eq!(el.checked_neg(), Some(-el.clone()));
+            assert!((el.checked_neg().unwrap() + &el).is_zero());
+        }
+
+        let corrupted = PrimeField4999 {
+            el: BigUint::from(5000u32),
+        };
+        assert_eq!(corrupted.checked_neg(), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not reduced")]
+    fn neg_of_unreduced() {
+        let corrupted = PrimeField4999 {
+            el: BigUint::from(4999u32),
+        };
+        let _ = -corrupted;
+    }
+
+    #[test]
+    fn canonical_representative() {
+        assert_eq!(
+            PrimeField4999::integer_embed(5000).to_biguint(),
+            BigUint::one()
+        );
+        assert_eq!(
+            PrimeField4999::integer_embed(-1).as_biguint(),
+            &BigUint::from(4998u32)
+        );
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let a = PrimeField4999::random(&mut rng);
+            assert!(a.as_biguint() < &PrimeField4999::characteristic());
+            assert_eq!(PrimeField4999::new(a.to_biguint()), a);
+        }
+    }
+
+    #[test]
+    fn arithmetic_stays_reduced() {
+        const ROUNDS: usize = 1000;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        assert!(PrimeField4999::zero().is_reduced());
+        assert!(PrimeField4999::one().is_reduced());
+        assert!(PrimeField4999::new(BigUint::from(123456789u32)).is_reduced());
+        assert!((-PrimeField4999::zero()).is_reduced());
+
+        for _ in 0..ROUNDS {
+            let a = PrimeField4999::random(&mut rng);
+            let b = PrimeField4999::random(&mut rng);
+            assert!(a.is_reduced());
+
+            assert!((a.clone() + b.clone()).is_reduced());
+            assert!((a.clone() - b.clone()).is_reduced());
+            assert!((a.clone() * b.clone()).is_reduced());
+            assert!((-a.clone()).is_reduced());
+            if let Some(inv) = a.invert() {
+                assert!(inv.is_reduced());
+            }
+            assert!(a.pow(-3).is_reduced());
+            assert!(a.scale(-3).is_reduced());
+
+            let mut c = a.clone();
+            c += &b;
+            assert!(c.is_reduced());
+            c -= &b;
+            assert!(c.is_reduced());
+            c *= &b;
+            assert!(c.is_reduced());
+        }
+    }
+
+    mod generate_many {
+        use super::*;
+
+        field_generate_many!(
+            (PrimeField7, BigUint::from(7u8)),
+            (PrimeField101, BigUint::from(101u8)),
+            (PrimeField65537, BigUint::from(65537u32)),
+        );
+
+        fn identities<F: Field + std::fmt::Debug>(characteristic: u32) {
+            let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+            assert_eq!(F::characteristic(), BigUint::from(characteristic));
+            assert!(F::integer_embed(characteristic).is_zero());
+            for _ in 0..100 {
+                let a = F::random_non_zero(&mut rng);
+                let b = F::random(&mut rng);
+                assert_eq!(a.clone() * a.invert().unwrap(), F::one());
+                assert_eq!(a.clone() + F::zero(), a);
+                assert_eq!((a.clone() + b.clone()) - b, a);
+            }
+        }
+
+        #[test]
+        fn shared_identities() {
+            identities::<PrimeField7>(7);
+            identities::<PrimeField101>(101);
+            identities::<PrimeField65537>(65537);
+        }
+    }
+
+    #[test]
+    fn signed_embeddings() {
+        assert_eq!(PrimeField4999::from_i64(-1), -PrimeField4999::one());
+        assert_eq!(
+            PrimeField4999::from_i64(5000),
+            PrimeField4999::integer_embed(1)
+        );
+
+        // integer_embed would overflow negating this
+        assert_eq!(
+            PrimeField4999::from_i64(i64::MIN),
+            PrimeField4999::integer_embed(26)
+        );
+
+        let big = num::BigInt::from(4999).pow(10u32) * 7 + 3;
+        assert_eq!(
+            PrimeField4999::from_bigint(&big),
+            PrimeField4999::integer_embed(3)
+        );
+        assert_eq!(
+            PrimeField4999::from_bigint(&-big),
+            PrimeField4999::integer_embed(-3)
+        );
+    }
+
+    #[test]
+    fn modulus_length() {
+        assert_eq!(PrimeField4999::modulus_bits(), 13);
+        assert_eq!(PrimeField4999::modulus_bytes(), 2);
+        assert_eq!(PrimeField65537::modulus_bits(), 17);
+        assert_eq!(PrimeField65537::modulus_bytes(), 3);
+        assert_eq!(PrimeField2e127m1::modulus_bytes(), 16);
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for el in vec![PrimeField4999::zero(), PrimeField4999::one()]
+            .into_iter()
+            .chain((0..100).map(|_| PrimeField4999::random(&mut rng)))
+        {
+            let bytes = el.to_bytes_le();
+            assert_eq!(bytes.len(), PrimeField4999::modulus_bytes());
+            assert_eq!(BigUint::from_bytes_le(&bytes), el.to_biguint());
+        }
+    }
+
+    #[test]
+    fn additive_order() {
+        assert_eq!(PrimeField4999::zero().additive_order(), BigUint::one());
+        for i in 1..4999u32 {
+            let el = PrimeField4999::new(BigUint::from(i));
+            let order = el.additive_order();
+            assert_eq!(order, PrimeField4999::characteristic());
+            assert!(el.scale(order).is_zero());
+        }
+    }
+
+    #[test]
+    fn multiplicative_group() {
+        let fact = PrimeField4999::multiplicative_group_factorization();
+        assert_eq!(fact.n(), BigUint::from(4998u32));
+
+        let g = PrimeField4999::multiplicative_generator();
+        assert_eq!(g, PrimeField4999::multiplicative_generator());
+        assert_eq!(g.multiplicative_order(), BigUint::from(4998u32));
+        // 3 is the smallest generator mod 4999
+        assert_eq!(g, PrimeField4999::integer_embed(3));
+        assert_eq!(
+            super::PRIMEFIELD4999_FACTORIZATION_CALLS.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+
+        assert_eq!(PrimeField4999::one().multiplicative_order(), BigUint::one());
+        assert_eq!(
+            (-PrimeField4999::one()).multiplicative_order(),
+            BigUint::from(2u8)
+        );
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let el = PrimeField4999::random_non_zero(&mut rng);
+            let order = el.multiplicative_order();
+            assert!(el.pow_biguint(&order).is_one());
+            for (q, _) in fact.iter() {
+                if num::Integer::is_multiple_of(&order, q) {
+                    assert!(!el.pow_biguint(&(order.clone() / q)).is_one());
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use contracts::requires;
-use num::{BigUint, Integer, One, Zero};
+use num::{BigInt, BigUint, Integer, One, Signed, Zero};
 
 #[derive(Debug)]
 pub struct GCDResult {
@@ -14,6 +14,61 @@ pub struct GCDResult {
     pub negative: bool,
 }
 
+impl GCDResult {
+    // Signed coefficients (s, t) such that s a + t n = d
+    fn signed_coefficients(&self) -> (BigInt, BigInt) {
+        let x = BigInt::from(self.a_coeff.clone());
+        let b = BigInt::from(self.n_coeff.clone());
+        if self.negative {
+            (-x, b)
+        } else {
+            (x, -b)
+        }
+    }
+
+    // Checks that this is a valid result of egcd(a, n), inputs can be in either order
+    pub fn verify(&self, a: &BigUint, n: &BigUint) -> bool {
+        let (a, n) = if a <= n { (a, n) } else { (n, a) };
+        if self.d != a.gcd(n) {
+            return false;
+        }
+
+        let (s, t) = self.signed_coefficients();
+        s * BigInt::from(a.clone()) + t * BigInt::from(n.clone()) == BigInt::from(self.d.clone())
+    }
+
+    // Rewrites the result as x a - n b = d (so negative is false) with 0 <= x < n / d,
+    // which forces 0 <= b < a / d. Inputs can be in either order.
+    // When a = 0 or a = n no such form exists, and we return - 0 a + n 1 = n instead.
+    pub fn normalized(self, a: &BigUint, n: &BigUint) -> GCDResult {
+        let (a, n) = if a <= n { (a, n) } else { (n, a) };
+        if a.is_zero() || a == n {
+            return GCDResult {
+                d: n.clone(),
+                a_coeff: BigUint::zero(),
+                n_coeff: BigUint::one(),
+                negative: true,
+            };
+        }
+
+        let a_int = BigInt::from(a.clone());
+        let n_int = BigInt::from(n.clone());
+        let d_int = BigInt::from(self.d.clone());
+
+        let (s, _) = self.signed_coefficients();
+        let x = s.mod_floor(&(n_int.clone() / &d_int));
+        let b = (x.clone() * a_int - d_int) / n_int;
+        debug_assert!(!b.is_negative());
+
+        GCDResult {
+            d: self.d,
+            a_coeff: x.to_biguint().unwrap(),
+            n_coeff: b.to_biguint().unwrap(),
+            negative: false,
+        }
+    }
+}
+
 pub fn egcd(a: BigUint, b: BigUint) -> GCDResult {
     if a <= b {
         egcd_impl(a, b)
@@ -22,6 +77,25 @@ pub fn egcd(a: BigUint, b: BigUint) -> GCDResult {
     }
 }
 
+// Also returns the (remainder, quotient) of every division step of the Euclidean
+// algorithm on (max, min), ending with the step whose remainder is zero
+pub fn egcd_trace(a: BigUint, n: BigUint) -> (GCDResult, Vec<(BigUint, BigUint)>) {
+    let (mut r_i_1, mut r_i) = if a <= n {
+        (n.clone(), a.clone())
+    } else {
+        (a.clone(), n.clone())
+    };
+
+    let mut trace = Vec::new();
+    while !r_i.is_zero() {
+        let (q_i, r_i_p_1) = r_i_1.div_mod_floor(&r_i);
+        trace.push((r_i_p_1.clone(), q_i));
+        r_i_1 = std::mem::replace(&mut r_i, r_i_p_1);
+    }
+
+    (egcd(a, n), trace)
+}
+
 #[requires(a <= n, "a must be smaller than n")]
 fn egcd_impl(a: BigUint, n: BigUint) -> GCDResult {
     if a.is_zero() {
@@ -49,50 +123,66 @@ fn egcd_impl(a: BigUint, n: BigUint) -> GCDResult {
 #[requires(a < n, "a must be smaller than n")]
 #[requires(!n.is_multiple_of(&a))]
 pub(crate) fn egcd_typical(a: BigUint, n: BigUint) -> GCDResult {
-    let mut qs = Vec::new();
-    let mut r_i_1 = n;
-    let mut r_i = a;
+    // Invariant: r_i = (-1)^(i + 1) s_i n + (-1)^i t_i a, where we only store the magnitudes
+    // r_{-1} = n, r_0 = a
+    let (mut r_i_1, mut r_i) = (n, a);
+    let (mut s_i_1, mut s_i) = (BigUint::one(), BigUint::zero());
+    let (mut t_i_1, mut t_i) = (BigUint::zero(), BigUint::one());
+    let mut i = 0usize;
 
     loop {
         let (q_i, r_i_p_1) = r_i_1.div_mod_floor(&r_i);
         if r_i_p_1.is_zero() {
             break;
         }
-        qs.push(q_i);
-        r_i_1 = r_i;
-        r_i = r_i_p_1;
-    }
 
-    let ell = qs.len() + 1;
+        let s_i_p_1 = s_i_1 + &q_i * &s_i;
+        let t_i_p_1 = t_i_1 + q_i * &t_i;
 
-    // The gcd
-    let d = r_i;
+        r_i_1 = std::mem::replace(&mut r_i, r_i_p_1);
+        s_i_1 = std::mem::replace(&mut s_i, s_i_p_1);
+        t_i_1 = std::mem::replace(&mut t_i, t_i_p_1);
+        i += 1;
+    }
 
-    let mut current_c = BigUint::one();
-    let mut current_d = qs[ell - 2].clone();
+    GCDResult {
+        d: r_i,
+        a_coeff: t_i,
+        n_coeff: s_i,
+        negative: i % 2 == 1,
+    }
+}
 
-    // We don't actually need the last element
-    qs.pop();
+// gcd of all the values, zero for the empty slice
+pub fn gcd_many(values: &[BigUint]) -> BigUint {
+    values
+        .iter()
+        .fold(BigUint::zero(), |acc, v| egcd(acc, v.clone()).d)
+}
 
-    for _ in 0..(ell - 2) {
-        let old_c = current_c.clone();
-        current_c = current_d.clone();
-        current_d = old_c + current_d * qs.pop().unwrap();
+// num / den in lowest terms, with a positive denominator
+pub fn reduce_fraction(num: &BigInt, den: &BigInt) -> (BigInt, BigInt) {
+    if den.is_zero() {
+        panic!("Zero denominator not allowed");
     }
 
-    GCDResult {
-        d,
-        a_coeff: current_d,
-        n_coeff: current_c,
-        negative: ell % 2 == 0,
+    // gcd is non negative, and non zero since den is not
+    let d = num.gcd(den);
+    let (num, den) = (num / &d, den / &d);
+    if den.is_negative() {
+        (-num, -den)
+    } else {
+        (num, den)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use num::{BigInt, BigUint, Integer, Zero};
+    use num::{BigInt, BigUint, Integer, One, Signed, Zero};
 
-    use super::egcd;
+    use super::{egcd, egcd_trace, egcd_typical, gcd_many, reduce_fraction, GCDResult};
+
+    const VERIFY_BOUND: u32 = 64;
 
     fn check_coefficients(mut a: BigUint, mut n: BigUint) {
         let res = egcd(a.clone(), n.clone());
@@ -127,4 +217,186 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_verify() {
+        for a in 0..VERIFY_BOUND {
+            for n in 0..VERIFY_BOUND {
+                let (a, n) = (BigUint::from(a), BigUint::from(n));
+                let res = egcd(a.clone(), n.clone());
+                assert!(res.verify(&a, &n));
+                assert!(res.verify(&n, &a));
+            }
+        }
+
+        let (a, n) = (BigUint::from(12u8), BigUint::from(18u8));
+        let mut res = egcd(a.clone(), n.clone());
+        res.a_coeff += 1u8;
+        assert!(!res.verify(&a, &n));
+    }
+
+    #[test]
+    fn test_normalized() {
+        for a in 0..VERIFY_BOUND {
+            for n in 0..VERIFY_BOUND {
+                let (a, n) = (BigUint::from(a), BigUint::from(n));
+                let res = egcd(a.clone(), n.clone()).normalized(&a, &n);
+                assert!(res.verify(&a, &n));
+
+                let (small, large) = if a <= n { (a, n) } else { (n, a) };
+                if !small.is_zero() && small != large {
+                    assert!(!res.negative);
+                    assert!(res.a_coeff < large.clone() / &res.d);
+                    assert!(res.n_coeff < small / &res.d);
+                }
+            }
+        }
+    }
+
+    // Reference implementation keeping every quotient around
+    fn egcd_typical_vec(a: BigUint, n: BigUint) -> GCDResult {
+        let mut qs = Vec::new();
+        let mut r_i_1 = n;
+        let mut r_i = a;
+
+        loop {
+            let (q_i, r_i_p_1) = r_i_1.div_mod_floor(&r_i);
+            if r_i_p_1.is_zero() {
+                break;
+            }
+            qs.push(q_i);
+            r_i_1 = r_i;
+            r_i = r_i_p_1;
+        }
+
+        let ell = qs.len() + 1;
+
+        // The gcd
+        let d = r_i;
+
+        let mut current_c = BigUint::one();
+        let mut current_d = qs[ell - 2].clone();
+
+        // We don't actually need the last element
+        qs.pop();
+
+        for _ in 0..(ell - 2) {
+            let old_c = current_c.clone();
+            current_c = current_d.clone();
+            current_d = old_c + current_d * qs.pop().unwrap();
+        }
+
+        GCDResult {
+            d,
+            a_coeff: current_d,
+            n_coeff: current_c,
+            negative: ell % 2 == 0,
+        }
+    }
+
+    #[test]
+    fn test_egcd_typical_matches_reference() {
+        for a in 1..256u32 {
+            for n in (a + 1)..256u32 {
+                let (a, n) = (BigUint::from(a), BigUint::from(n));
+                if n.is_multiple_of(&a) {
+                    continue;
+                }
+
+                let res = egcd_typical(a.clone(), n.clone());
+                let reference = egcd_typical_vec(a.clone(), n.clone());
+                assert!(res.verify(&a, &n));
+                assert_eq!(res.d, reference.d);
+                assert_eq!(res.a_coeff, reference.a_coeff);
+                assert_eq!(res.n_coeff, reference.n_coeff);
+                assert_eq!(res.negative, reference.negative);
+            }
+        }
+    }
+
+    #[test]
+    fn test_egcd_trace() {
+        let (res, trace) = egcd_trace(BigUint::from(240u32), BigUint::from(46u32));
+        let expected: Vec<_> = [(10u32, 5u32), (6, 4), (4, 1), (2, 1), (0, 2)]
+            .iter()
+            .map(|(r, q)| (BigUint::from(*r), BigUint::from(*q)))
+            .collect();
+        assert_eq!(trace, expected);
+        assert_eq!(res.d, BigUint::from(2u8));
+
+        assert!(egcd_trace(BigUint::from(5u8), BigUint::zero()).1.is_empty());
+    }
+
+    #[test]
+    fn test_egcd_trace_reconstructs_coefficients() {
+        for a in 1..VERIFY_BOUND {
+            for n in (a + 1)..VERIFY_BOUND {
+                let (a_big, n_big) = (BigUint::from(a), BigUint::from(n));
+                if n_big.is_multiple_of(&a_big) {
+                    continue;
+                }
+
+                let (res, trace) = egcd_trace(a_big.clone(), n_big.clone());
+
+                // s n + t a = r for every remainder, stop at the last non zero one
+                let (mut s_i_1, mut s_i) = (BigInt::one(), BigInt::zero());
+                let (mut t_i_1, mut t_i) = (BigInt::zero(), BigInt::one());
+                for (_, q) in &trace[..trace.len() - 1] {
+                    let q = BigInt::from(q.clone());
+                    let s_next = &s_i_1 - &q * &s_i;
+                    let t_next = &t_i_1 - &q * &t_i;
+                    s_i_1 = std::mem::replace(&mut s_i, s_next);
+                    t_i_1 = std::mem::replace(&mut t_i, t_next);
+                }
+
+                let sign = if res.negative { -1 } else { 1 };
+                assert_eq!(t_i, BigInt::from(res.a_coeff) * sign);
+                assert_eq!(s_i, BigInt::from(res.n_coeff) * -sign);
+                assert_eq!(&trace[trace.len() - 2].0, &res.d);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reduce_fraction() {
+        let frac = |n: i64, d: i64| reduce_fraction(&BigInt::from(n), &BigInt::from(d));
+        let pair = |n: i64, d: i64| (BigInt::from(n), BigInt::from(d));
+
+        assert_eq!(frac(6, -4), pair(-3, 2));
+        assert_eq!(frac(-6, -4), pair(3, 2));
+        assert_eq!(frac(3, 2), pair(3, 2));
+        assert_eq!(frac(0, -7), pair(0, 1));
+        assert_eq!(frac(5, 1), pair(5, 1));
+
+        for n in -30..30i64 {
+            for d in (-30..30i64).filter(|d| *d != 0) {
+                let (r_n, r_d) = frac(n, d);
+                assert!(r_d.is_positive());
+                assert!(r_n.gcd(&r_d).is_one());
+                assert_eq!(r_n * d, r_d * n);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gcd_many() {
+        let values: Vec<_> = [12u32, 18, 24].iter().map(|v| BigUint::from(*v)).collect();
+        assert_eq!(gcd_many(&values), BigUint::from(6u8));
+        assert_eq!(gcd_many(&[]), BigUint::zero());
+        assert_eq!(gcd_many(&[BigUint::from(42u8)]), BigUint::from(42u8));
+        assert_eq!(
+            gcd_many(&[BigUint::zero(), BigUint::from(15u8), BigUint::from(10u8)]),
+            BigUint::from(5u8)
+        );
+        assert_eq!(
+            gcd_many(&[BigUint::from(7u8), BigUint::from(9u8), BigUint::from(12u8)]),
+            BigUint::one()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reduce_fraction_zero_denominator() {
+        reduce_fraction(&BigInt::from(1), &BigInt::zero());
+    }
 }
@@ -0,0 +1,114 @@
+use num::{BigInt, BigUint, Integer, Zero};
+
+use crate::{fields::PrimeField, num::mod_inverse, poly::DensePolynomial};
+
+// Coefficients of f as integers, taking the representative in (-p/2, p/2]
+// so that small negative integers survive the round trip through the field
+fn integer_coefficients<F: PrimeField>(f: &DensePolynomial<F>) -> Vec<BigInt> {
+    let char = BigInt::from(F::characteristic());
+    let half = char.clone() / 2u8;
+    let n = f.degree().map(|d| d + 1).unwrap_or(0);
+    (0..n)
+        .map(|i| {
+            let c = BigInt::from(f.coeff(i).to_biguint());
+            if c > half {
+                c - &char
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+fn evaluate_mod(coeffs: &[BigInt], x: &BigInt, modulus: &BigInt) -> BigInt {
+    coeffs
+        .iter()
+        .rev()
+        .fold(BigInt::zero(), |acc, c| (acc * x + c).mod_floor(modulus))
+}
+
+// Lifts a simple root of f mod p to a root mod p^k, with f read as an integer polynomial
+// Returns None if root is not a root mod p, or if it is singular (f'(root) = 0 mod p)
+pub fn hensel_lift_root<F: PrimeField>(
+    f: &DensePolynomial<F>,
+    root: &BigUint,
+    p: &BigUint,
+    k: u32,
+) -> Option<BigUint> {
+    let coeffs = integer_coefficients(f);
+    let derivative: Vec<_> = coeffs
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| c * i)
+        .collect();
+
+    let p_int = BigInt::from(p.clone());
+    let mut r = BigInt::from(root.clone()).mod_floor(&p_int);
+    if !evaluate_mod(&coeffs, &r, &p_int).is_zero() {
+        return None;
+    }
+    if evaluate_mod(&derivative, &r, &p_int).is_zero() {
+        return None;
+    }
+
+    // Newton step r <- r - f(r) / f'(r) mod p^(j + 1)
+    let mut modulus = p_int.clone();
+    for _ in 1..k {
+        modulus *= &p_int;
+        let value = evaluate_mod(&coeffs, &r, &modulus);
+        let slope = evaluate_mod(&derivative, &r, &modulus)
+            .to_biguint()
+            .unwrap();
+        let inv = mod_inverse(&slope, &modulus.to_biguint().unwrap())?;
+        r = (r - value * BigInt::from(inv)).mod_floor(&modulus);
+    }
+
+    r.to_biguint()
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigUint, Integer, Zero};
+
+    use super::hensel_lift_root;
+    use crate::{fields::primefields::PrimeField4999, poly::DensePolynomial};
+
+    #[test]
+    fn lift_square_root_of_two() {
+        // x^2 - 2
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![-2, 0, 1]);
+        let p = BigUint::from(7u8);
+        for root in [3u8, 4] {
+            let root = BigUint::from(root);
+            for k in 1..6 {
+                let modulus = p.pow(k);
+                let lifted = hensel_lift_root(&f, &root, &p, k).unwrap();
+                assert!(lifted < modulus);
+                assert_eq!(lifted.clone() % &p, root);
+                let value = lifted.clone() * lifted + &modulus - 2u8;
+                assert!(value.is_multiple_of(&modulus));
+            }
+        }
+
+        let lifted = hensel_lift_root(&f, &BigUint::from(3u8), &p, 2).unwrap();
+        assert_eq!(lifted, BigUint::from(10u8));
+    }
+
+    #[test]
+    fn singular_and_non_roots() {
+        let p = BigUint::from(7u8);
+
+        // x^2 has a double root at zero
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![0, 0, 1]);
+        assert!(hensel_lift_root(&f, &BigUint::zero(), &p, 3).is_none());
+
+        // (x - 1)^2
+        let g: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, -2, 1]);
+        assert!(hensel_lift_root(&g, &BigUint::from(1u8), &p, 3).is_none());
+
+        // 2 is not a root of x^2 - 2 mod 7
+        let h: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![-2, 0, 1]);
+        assert!(hensel_lift_root(&h, &BigUint::from(2u8), &p, 3).is_none());
+    }
+}
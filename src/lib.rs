@@ -1,11 +1,14 @@
 #![feature(bigint_helper_methods)]
 #![feature(generic_const_exprs)]
 
+pub mod arithmetic_functions;
 pub(crate) mod double_and_add;
 pub mod ec;
 pub mod factorization;
 pub mod fields;
 pub mod gcd;
+pub mod hensel;
+pub mod linalg;
 pub mod num;
 pub mod poly;
 pub mod primes;
@@ -0,0 +1,165 @@
+use crate::{fields::Field, rings::Ring};
+
+// Dense row major matrix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<F> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<F>,
+}
+
+impl<F> Matrix<F>
+where
+    F: Ring,
+{
+    pub fn new(rows: usize, cols: usize, entries: Vec<F>) -> Self {
+        if entries.len() != rows * cols {
+            panic!("Expected {} entries, got {}", rows * cols, entries.len());
+        }
+        Matrix {
+            rows,
+            cols,
+            entries,
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut entries = vec![F::zero(); n * n];
+        for i in 0..n {
+            entries[i * n + i] = F::one();
+        }
+        Self::new(n, n, entries)
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> &F {
+        &self.entries[i * self.cols + j]
+    }
+
+    pub fn mul_vec(&self, v: &[F]) -> Vec<F> {
+        if v.len() != self.cols {
+            panic!("Dimension mismatch");
+        }
+        (0..self.rows)
+            .map(|i| {
+                v.iter()
+                    .enumerate()
+                    .fold(F::zero(), |acc, (j, x)| acc + self.get(i, j).clone() * x)
+            })
+            .collect()
+    }
+}
+
+impl<F> Matrix<F>
+where
+    F: Field,
+{
+    // Gauss-Jordan elimination, None if the matrix is singular
+    pub fn invert(&self) -> Option<Self> {
+        if self.rows != self.cols {
+            panic!("Only square matrices can be inverted");
+        }
+
+        let n = self.rows;
+        let mut a = self.entries.clone();
+        let mut inv = Self::identity(n).entries;
+
+        for col in 0..n {
+            let pivot = (col..n).find(|&r| !a[r * n + col].is_zero())?;
+            for j in 0..n {
+                a.swap(pivot * n + j, col * n + j);
+                inv.swap(pivot * n + j, col * n + j);
+            }
+
+            let normalizer = a[col * n + col].invert().unwrap();
+            for j in 0..n {
+                a[col * n + j] *= normalizer.clone();
+                inv[col * n + j] *= normalizer.clone();
+            }
+
+            for r in (0..n).filter(|&r| r != col) {
+                let factor = a[r * n + col].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                for j in 0..n {
+                    let (pivot_a, pivot_inv) = (a[col * n + j].clone(), inv[col * n + j].clone());
+                    a[r * n + j] += -(factor.clone() * pivot_a);
+                    inv[r * n + j] += -(factor.clone() * pivot_inv);
+                }
+            }
+        }
+
+        Some(Self::new(n, n, inv))
+    }
+}
+
+// V[i][j] = points[i]^j, maps coefficient vectors to evaluations at the points
+pub fn vandermonde<F: Ring>(points: &[F]) -> Matrix<F> {
+    let n = points.len();
+    let mut entries = Vec::with_capacity(n * n);
+    for x in points {
+        let mut pow = F::one();
+        for _ in 0..n {
+            entries.push(pow.clone());
+            pow = pow * x;
+        }
+    }
+    Matrix::new(n, n, entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fields::primefields::PrimeField4999;
+    use rand::SeedableRng;
+
+    #[test]
+    fn inversion() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in 1..8 {
+            let m = Matrix::new(
+                n,
+                n,
+                (0..n * n)
+                    .map(|_| PrimeField4999::random(&mut rng))
+                    .collect(),
+            );
+            let inv = m.invert().unwrap();
+            let v: Vec<_> = (0..n).map(|_| PrimeField4999::random(&mut rng)).collect();
+            assert_eq!(inv.mul_vec(&m.mul_vec(&v)), v);
+        }
+
+        let singular: Matrix<PrimeField4999> = Matrix::new(
+            2,
+            2,
+            vec![1, 2, 2, 4]
+                .into_iter()
+                .map(PrimeField4999::integer_embed)
+                .collect(),
+        );
+        assert!(singular.invert().is_none());
+    }
+
+    #[test]
+    fn vandermonde_singular_iff_repeated() {
+        let points: Vec<_> = [1, 2, 3, 4]
+            .iter()
+            .map(|&x| PrimeField4999::integer_embed(x))
+            .collect();
+        assert!(vandermonde(&points).invert().is_some());
+
+        let repeated: Vec<_> = [1, 2, 3, 2]
+            .iter()
+            .map(|&x| PrimeField4999::integer_embed(x))
+            .collect();
+        assert!(vandermonde(&repeated).invert().is_none());
+    }
+}
@@ -0,0 +1,83 @@
+use ::num::{BigUint, Zero};
+
+// Barrett reduction modulo a fixed p of k bits, valid for inputs below 2^(2k)
+// which includes all products of two reduced elements
+#[derive(Debug, Clone)]
+pub struct BarrettReducer {
+    modulus: BigUint,
+    // floor(2^(2k) / p)
+    mu: BigUint,
+    k: u64,
+}
+
+impl BarrettReducer {
+    pub fn new(modulus: BigUint) -> Self {
+        if modulus.is_zero() {
+            panic!("Zero modulus not allowed");
+        }
+
+        let k = modulus.bits();
+        let mu = (BigUint::from(1u8) << (2 * k)) / &modulus;
+        BarrettReducer { modulus, mu, k }
+    }
+
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    pub fn reduce(&self, x: BigUint) -> BigUint {
+        if x.bits() > 2 * self.k {
+            return x % &self.modulus;
+        }
+
+        // q is at most two less than floor(x / p)
+        let q = ((&x >> (self.k - 1)) * &self.mu) >> (self.k + 1);
+        let mut r = x - q * &self.modulus;
+        for _ in 0..2 {
+            if r >= self.modulus {
+                r -= &self.modulus;
+            }
+        }
+        debug_assert!(r < self.modulus);
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::num::{bigint::RandBigInt, BigUint};
+    use rand::SeedableRng;
+
+    use super::BarrettReducer;
+
+    #[test]
+    fn matches_remainder() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let moduli = [
+            BigUint::from(1u8),
+            BigUint::from(2u8),
+            BigUint::from(3u8),
+            BigUint::from(4999u32),
+            BigUint::from(65536u32),
+            BigUint::from(u64::MAX),
+            BigUint::from(u128::MAX >> 1),
+        ];
+
+        for p in moduli {
+            let reducer = BarrettReducer::new(p.clone());
+            for x in 0..100u32 {
+                assert_eq!(reducer.reduce(BigUint::from(x)), BigUint::from(x) % &p);
+            }
+
+            let square = p.clone() * &p;
+            for _ in 0..1000 {
+                let x = rng.gen_biguint_below(&square);
+                assert_eq!(reducer.reduce(x.clone()), x % &p);
+            }
+
+            // Beyond the Barrett range we still get the right answer
+            let x = rng.gen_biguint(4 * p.bits() + 10);
+            assert_eq!(reducer.reduce(x.clone()), x % &p);
+        }
+    }
+}
@@ -54,6 +54,197 @@ impl<const LIMBS: usize> FixedInteger<LIMBS> {
     pub fn sub_self_with_borrow(&mut self, rhs: &Self) -> bool {
         ops::sub_self(&mut self.0, &rhs.0)
     }
+
+    // Same names and semantics as for the primitive integer types
+
+    pub fn overflowing_add(&self, rhs: &Self) -> (Self, bool) {
+        self.add_with_carry(rhs)
+    }
+
+    pub fn wrapping_add(&self, rhs: &Self) -> Self {
+        self.add_with_carry(rhs).0
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        match self.add_with_carry(rhs) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    pub fn overflowing_sub(&self, rhs: &Self) -> (Self, bool) {
+        self.sub_with_borrow(rhs)
+    }
+
+    pub fn wrapping_sub(&self, rhs: &Self) -> Self {
+        self.sub_with_borrow(rhs).0
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        match self.sub_with_borrow(rhs) {
+            (res, false) => Some(res),
+            (_, true) => None,
+        }
+    }
+
+    // The product modulo 2^(64 LIMBS)
+    pub fn wrapping_mul(&self, rhs: &Self) -> Self {
+        let mut arr = [0x0; LIMBS];
+        ops::mul_low(&self.0, &rhs.0, &mut arr);
+        FixedInteger(arr)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.iter().all(|&limb| limb == 0)
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub fn trailing_zeros(&self) -> u32 {
+        let mut zeros = 0;
+        for limb in self.0.iter() {
+            if *limb != 0 {
+                return zeros + limb.trailing_zeros();
+            }
+            zeros += 64;
+        }
+        zeros
+    }
+
+    // Shifts in zeros, so shifting by 64 LIMBS or more gives zero
+    pub fn shr(&self, bits: u32) -> Self {
+        let (limbs, bits) = ((bits / 64) as usize, bits % 64);
+        let mut arr = [0x0; LIMBS];
+        for (i, limb) in arr.iter_mut().take(LIMBS.saturating_sub(limbs)).enumerate() {
+            *limb = self.0[i + limbs] >> bits;
+            if bits > 0 && i + limbs + 1 < LIMBS {
+                *limb |= self.0[i + limbs + 1] << (64 - bits);
+            }
+        }
+        FixedInteger(arr)
+    }
+
+    // Reduction modulo 2^bits
+    fn low_bits(&self, bits: u32) -> Self {
+        let mut arr = self.0;
+        for (i, limb) in arr.iter_mut().enumerate() {
+            let start = 64 * i as u32;
+            if bits <= start {
+                *limb = 0;
+            } else if bits - start < 64 {
+                *limb &= (1 << (bits - start)) - 1;
+            }
+        }
+        FixedInteger(arr)
+    }
+
+    // Halves, with the carry as the new top bit
+    fn half_with_carry(&self, carry: bool) -> Self {
+        let mut res = self.shr(1);
+        if carry {
+            res.0[LIMBS - 1] |= 1 << 63;
+        }
+        res
+    }
+
+    // Returns x in [0, modulus) with self x = 1 mod modulus, if it exists
+    // Writing modulus = 2^k m with m odd, inverts modulo m by binary extended gcd,
+    // modulo 2^k by Newton iteration, and then combines the two
+    pub fn mod_inverse(&self, modulus: &Self) -> Option<Self> {
+        if modulus.is_zero() {
+            panic!("Zero modulus not allowed");
+        }
+
+        let k = modulus.trailing_zeros();
+        let odd_part = modulus.shr(k);
+        let odd_inverse = self.odd_mod_inverse(&odd_part)?;
+        if k == 0 {
+            return Some(odd_inverse);
+        }
+        if !self.is_odd() {
+            return None;
+        }
+
+        // odd_inverse + odd_part t is still the inverse modulo odd_part, and is below modulus
+        let two_inverse = self.wrapping_inverse().low_bits(k);
+        let t = (two_inverse - odd_inverse) * odd_part.wrapping_inverse();
+        Some(odd_inverse + odd_part * t.low_bits(k))
+    }
+
+    // Keeps x_1 self = u and x_2 self = v modulo the odd modulus, while reducing u and v
+    fn odd_mod_inverse(&self, modulus: &Self) -> Option<Self> {
+        debug_assert!(modulus.is_odd());
+        if *modulus == Self::one() {
+            return Some(Self::zero());
+        }
+
+        // Division by two and subtraction modulo modulus, without overflowing
+        let half = |x: Self| {
+            if x.is_odd() {
+                let (sum, carry) = x.add_with_carry(modulus);
+                sum.half_with_carry(carry)
+            } else {
+                x.shr(1)
+            }
+        };
+        let sub = |x: Self, y: &Self| match x.sub_with_borrow(y) {
+            (res, true) => res + modulus,
+            (res, false) => res,
+        };
+
+        let (mut u, mut v) = (*self, *modulus);
+        let (mut x_1, mut x_2) = (Self::one(), Self::zero());
+        while !u.is_zero() {
+            while !u.is_odd() {
+                u = u.shr(1);
+                x_1 = half(x_1);
+            }
+            while !v.is_odd() {
+                v = v.shr(1);
+                x_2 = half(x_2);
+            }
+            if u >= v {
+                u -= v;
+                x_1 = sub(x_1, &x_2);
+            } else {
+                v -= u;
+                x_2 = sub(x_2, &x_1);
+            }
+        }
+
+        // v is now the gcd
+        if v == Self::one() {
+            Some(x_2)
+        } else {
+            None
+        }
+    }
+
+    // The inverse modulo 2^(64 LIMBS) of an odd integer
+    fn wrapping_inverse(&self) -> Self {
+        debug_assert!(self.is_odd());
+        // Correct modulo 8, and each step doubles the number of correct bits
+        let mut x = *self;
+        while self.wrapping_mul(&x) != Self::one() {
+            x = x * (Self::from_u64(2) - *self * x);
+        }
+        x
+    }
+}
+
+// Compares the most significant limbs first
+impl<const LIMBS: usize> Ord for FixedInteger<LIMBS> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const LIMBS: usize> PartialOrd for FixedInteger<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
 impl<const LIMBS: usize> std::ops::AddAssign for FixedInteger<LIMBS> {
@@ -108,10 +299,26 @@ impl<'a, const LIMBS: usize> std::ops::Sub<&'a Self> for FixedInteger<LIMBS> {
     }
 }
 
+impl<const LIMBS: usize> std::ops::Mul for FixedInteger<LIMBS> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.wrapping_mul(&rhs)
+    }
+}
+
+impl<const LIMBS: usize> std::ops::Mul<&Self> for FixedInteger<LIMBS> {
+    type Output = Self;
+    fn mul(self, rhs: &Self) -> Self {
+        self.wrapping_mul(rhs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::FixedInteger;
-    use rand::SeedableRng;
+    use crate::num::mod_inverse;
+    use num::{BigUint, One};
+    use rand::{RngCore, SeedableRng};
     const ITERATIONS: usize = 1000;
 
     type IntType = FixedInteger<4>;
@@ -156,4 +363,117 @@ mod tests {
             assert_eq!(el1 + el2 - el2, el1)
         }
     }
+
+    #[test]
+    fn std_style_overflow() {
+        let max = IntType::maxvalue();
+        let one = IntType::one();
+        let zero = IntType::zero();
+
+        assert_eq!(max.checked_add(&one), None);
+        assert_eq!(max.wrapping_add(&one), zero);
+        assert_eq!(max.overflowing_add(&one), (zero, true));
+        assert_eq!(zero.checked_add(&one), Some(one));
+        assert_eq!(zero.overflowing_add(&one), (one, false));
+
+        assert_eq!(zero.checked_sub(&one), None);
+        assert_eq!(zero.wrapping_sub(&one), max);
+        assert_eq!(zero.overflowing_sub(&one), (max, true));
+        assert_eq!(max.checked_sub(&one), Some(max - one));
+        assert_eq!(one.overflowing_sub(&one), (zero, false));
+    }
+
+    fn to_biguint(x: &IntType) -> BigUint {
+        let bytes: Vec<u8> = x.0.iter().flat_map(|limb| limb.to_le_bytes()).collect();
+        BigUint::from_bytes_le(&bytes)
+    }
+
+    #[test]
+    fn wrapping_mul_matches_biguint() {
+        let modulus = BigUint::from(1u8) << 256;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..ITERATIONS {
+            let el1 = IntType::random(&mut rng);
+            let el2 = IntType::random(&mut rng);
+            let expected = to_biguint(&el1) * to_biguint(&el2) % &modulus;
+            assert_eq!(to_biguint(&(el1 * el2)), expected);
+            assert_eq!(el1 * el2, el2.wrapping_mul(&el1));
+        }
+
+        let max = IntType::maxvalue();
+        let expected = to_biguint(&max) * to_biguint(&max) % &modulus;
+        assert_eq!(to_biguint(&max.wrapping_mul(&max)), expected);
+        assert_eq!(max * max, IntType::one());
+        assert_eq!(max * IntType::zero(), IntType::zero());
+        assert_eq!(max * IntType::one(), max);
+
+        // 2^128 * 2^128 wraps to zero
+        let half = FixedInteger([0, 0, 1, 0]);
+        assert_eq!(half * half, IntType::zero());
+    }
+
+    #[test]
+    fn ordering_and_shifts() {
+        let high = FixedInteger([0, 0, 0, 1]);
+        let low = FixedInteger([u64::MAX, u64::MAX, u64::MAX, 0]);
+        assert!(low < high);
+        assert_eq!(high.shr(193), IntType::zero());
+        assert_eq!(high.shr(192), IntType::one());
+        assert_eq!(high.shr(1), FixedInteger([0, 0, 1 << 63, 0]));
+        assert_eq!(high.trailing_zeros(), 192);
+        assert_eq!(IntType::zero().trailing_zeros(), 256);
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..ITERATIONS {
+            let el1 = IntType::random(&mut rng);
+            let el2 = IntType::random(&mut rng);
+            assert_eq!(el1.cmp(&el2), to_biguint(&el1).cmp(&to_biguint(&el2)));
+            let shift = rng.next_u32() % 256;
+            assert_eq!(to_biguint(&el1.shr(shift)), to_biguint(&el1) >> shift);
+        }
+    }
+
+    #[test]
+    fn mod_inverse_matches_biguint() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let mut inverted = 0;
+        for i in 0..ITERATIONS {
+            let a = IntType::random(&mut rng);
+            let mut modulus = IntType::random(&mut rng);
+            // Also exercise even moduli, and moduli with many factors of two
+            if i % 2 == 0 {
+                modulus = modulus.low_bits(255) + IntType::one();
+            }
+            if i % 4 == 0 {
+                modulus = modulus.low_bits(100 + (i % 50) as u32);
+            }
+
+            let (a_big, modulus_big) = (to_biguint(&a), to_biguint(&modulus));
+            let expected = mod_inverse(&a_big, &modulus_big);
+            let inverse = a.mod_inverse(&modulus);
+            assert_eq!(inverse.map(|x| to_biguint(&x)), expected);
+
+            if let Some(inverse) = inverse {
+                inverted += 1;
+                assert!(inverse < modulus);
+                assert!((a_big * to_biguint(&inverse) % &modulus_big).is_one());
+            }
+        }
+        assert!(inverted > ITERATIONS / 4);
+
+        // 2^k moduli, and non coprime inputs
+        let two_power = FixedInteger([0, 0, 1, 0]);
+        let three = IntType::from_u64(3);
+        let inverse = three.mod_inverse(&two_power).unwrap();
+        assert!(inverse < two_power);
+        assert_eq!((inverse * three).low_bits(128), IntType::one());
+        assert_eq!(IntType::from_u64(6).mod_inverse(&two_power), None);
+        assert_eq!(
+            IntType::from_u64(6).mod_inverse(&IntType::from_u64(9)),
+            None
+        );
+        assert_eq!(IntType::zero().mod_inverse(&IntType::from_u64(9)), None);
+        assert_eq!(three.mod_inverse(&IntType::one()), Some(IntType::zero()));
+        assert_eq!(three.mod_inverse(&IntType::maxvalue()), None);
+    }
 }
@@ -1,2 +1,173 @@
+pub mod barrett;
 pub mod fixed;
+pub mod modular;
 pub mod ops;
+
+use ::num::{BigInt, BigUint, One, Signed, Zero};
+
+use crate::gcd::egcd;
+
+// Computes base^exp mod modulus, where base^0 = 1 (so that anything mod 1 is zero)
+// Panics if the modulus is zero
+pub fn mod_pow(base: &BigUint, exp: &BigUint, modulus: &BigUint) -> BigUint {
+    if modulus.is_zero() {
+        panic!("Zero modulus not allowed");
+    }
+
+    if modulus.is_one() {
+        return BigUint::zero();
+    }
+
+    base.modpow(exp, modulus)
+}
+
+// As above, but negative exponents raise the inverse of the base. None if that does not exist
+pub fn mod_pow_signed(base: &BigUint, exp: &BigInt, modulus: &BigUint) -> Option<BigUint> {
+    if exp.is_negative() {
+        let inv = mod_inverse(base, modulus)?;
+        Some(mod_pow(&inv, exp.magnitude(), modulus))
+    } else {
+        Some(mod_pow(base, exp.magnitude(), modulus))
+    }
+}
+
+// Returns x in [0, modulus) with a x = 1 mod modulus, if it exists
+pub fn mod_inverse(a: &BigUint, modulus: &BigUint) -> Option<BigUint> {
+    if modulus.is_zero() {
+        panic!("Zero modulus not allowed");
+    }
+
+    if modulus.is_one() {
+        return Some(BigUint::zero());
+    }
+
+    let a = a % modulus;
+    let res = egcd(a, modulus.clone());
+    if !res.d.is_one() {
+        return None;
+    }
+
+    // a is the smaller, so a_coeff is its coefficient
+    let x = res.a_coeff % modulus;
+    if res.negative && !x.is_zero() {
+        Some(modulus - x)
+    } else {
+        Some(x)
+    }
+}
+
+// The x in [0, prod n_i) with x = a_i mod n_i, for pairwise coprime n_i. None if they are not
+pub fn crt(congruences: &[(BigUint, BigUint)]) -> Option<BigUint> {
+    let mut x = BigUint::zero();
+    let mut modulus = BigUint::one();
+    for (a, n) in congruences {
+        // x + modulus k = a mod n
+        let inv = mod_inverse(&modulus, n)?;
+        let diff = (a % n + n - &x % n) % n;
+        let k = diff * inv % n;
+        x += &modulus * k;
+        modulus *= n;
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use ::num::{BigInt, BigUint, Integer, One, Zero};
+
+    use super::{crt, mod_inverse, mod_pow, mod_pow_signed};
+
+    #[test]
+    fn test_mod_pow() {
+        let p = BigUint::from(4999u32);
+        for base in 0..50u32 {
+            let base = BigUint::from(base);
+            for exp in 0..50u32 {
+                assert_eq!(mod_pow(&base, &BigUint::from(exp), &p), base.pow(exp) % &p);
+            }
+        }
+
+        assert_eq!(
+            mod_pow(&BigUint::zero(), &BigUint::zero(), &p),
+            BigUint::one()
+        );
+    }
+
+    #[test]
+    fn test_modulus_one() {
+        let one = BigUint::one();
+        for base in 0..10u32 {
+            for exp in 0..10u32 {
+                let (base, exp) = (BigUint::from(base), BigUint::from(exp));
+                assert!(mod_pow(&base, &exp, &one).is_zero());
+                let exp = BigInt::from(exp);
+                assert!(mod_pow_signed(&base, &-exp, &one).unwrap().is_zero());
+            }
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        for n in 1..100u32 {
+            let n = BigUint::from(n);
+            for a in 0..100u32 {
+                let a = BigUint::from(a);
+                match mod_inverse(&a, &n) {
+                    Some(inv) => {
+                        assert!(inv < n);
+                        assert_eq!((a * inv) % &n, BigUint::one() % &n);
+                    }
+                    None => assert!(!a.gcd(&n).is_one()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_negative_exponents() {
+        let p = BigUint::from(4999u32);
+        for base in 1..50u32 {
+            let base = BigUint::from(base);
+            for exp in 0..50u32 {
+                let pos = mod_pow_signed(&base, &BigInt::from(exp), &p).unwrap();
+                let neg = mod_pow_signed(&base, &-BigInt::from(exp), &p).unwrap();
+                assert!((pos * neg % &p).is_one());
+            }
+        }
+
+        // 5 is not invertible mod 25
+        let n = BigUint::from(25u8);
+        let five = BigUint::from(5u8);
+        assert!(mod_pow_signed(&five, &BigInt::from(-1), &n).is_none());
+        assert_eq!(
+            mod_pow_signed(&five, &BigInt::from(1), &n),
+            Some(five.clone())
+        );
+        assert_eq!(
+            mod_pow_signed(&BigUint::from(2u8), &BigInt::from(-1), &n),
+            Some(BigUint::from(13u8))
+        );
+    }
+
+    #[test]
+    fn test_crt() {
+        let congruences = |v: &[(u32, u32)]| -> Vec<(BigUint, BigUint)> {
+            v.iter()
+                .map(|(a, n)| (BigUint::from(*a), BigUint::from(*n)))
+                .collect()
+        };
+
+        assert_eq!(
+            crt(&congruences(&[(2, 3), (3, 5), (2, 7)])),
+            Some(BigUint::from(23u8))
+        );
+        assert_eq!(crt(&[]), Some(BigUint::zero()));
+        assert_eq!(crt(&congruences(&[(1, 4), (1, 6)])), None);
+
+        let moduli = [4u32, 9, 5, 7, 11];
+        for x in 0..(4 * 9 * 5 * 7 * 11u32) {
+            let v: Vec<_> = moduli.iter().map(|n| (x % n, *n)).collect();
+            assert_eq!(crt(&congruences(&v)), Some(BigUint::from(x)));
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Arithmetic on representatives already reduced into [0, modulus),
+// shared by the generated rings and fields
+use ::num::{BigUint, Zero};
+
+pub fn add_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    let res = a + b;
+    if &res >= modulus {
+        res - modulus
+    } else {
+        res
+    }
+}
+
+pub fn neg_mod(a: &BigUint, modulus: &BigUint) -> BigUint {
+    if a.is_zero() {
+        BigUint::zero()
+    } else {
+        modulus - a
+    }
+}
+
+pub fn sub_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    if a >= b {
+        a - b
+    } else {
+        modulus - (b - a)
+    }
+}
+
+pub fn mul_mod(a: &BigUint, b: &BigUint, modulus: &BigUint) -> BigUint {
+    (a * b) % modulus
+}
+
+#[cfg(test)]
+mod tests {
+    use ::num::{bigint::RandBigInt, BigUint};
+    use rand::SeedableRng;
+
+    use super::{add_mod, mul_mod, neg_mod, sub_mod};
+    use crate::fields::{primefields::PrimeField4999, PrimeField};
+    use crate::rings::integers_mod_ring::IntegersMod425;
+
+    const ROUNDS: usize = 1000;
+
+    // Reductions written out independently of the helpers
+    #[test]
+    fn matches_reference() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for &modulus in [4999u32, 425].iter() {
+            let p = BigUint::from(modulus);
+            for _ in 0..ROUNDS {
+                let (a, b) = (rng.gen_biguint_below(&p), rng.gen_biguint_below(&p));
+                assert_eq!(add_mod(&a, &b, &p), (&a + &b) % &p);
+                assert_eq!(sub_mod(&a, &b, &p), (&p + &a - &b) % &p);
+                assert_eq!(mul_mod(&a, &b, &p), (&a * &b) % &p);
+                assert_eq!(neg_mod(&a, &p), (&p - &a) % &p);
+            }
+
+            // Edges of the range
+            let top = &p - 1u8;
+            let zero = BigUint::from(0u8);
+            assert_eq!(add_mod(&top, &top, &p), &p - 2u8);
+            assert_eq!(sub_mod(&zero, &top, &p), BigUint::from(1u8));
+            assert_eq!(neg_mod(&zero, &p), zero);
+        }
+    }
+
+    // The operators of the generated types, which delegate to the helpers, against the same reductions
+    #[test]
+    fn generated_types_match_reference() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+
+        let p = BigUint::from(4999u32);
+        for _ in 0..ROUNDS {
+            let (a, b) = (rng.gen_biguint_below(&p), rng.gen_biguint_below(&p));
+            let (x, y) = (
+                PrimeField4999::new(a.clone()),
+                PrimeField4999::new(b.clone()),
+            );
+            assert_eq!((x.clone() + y.clone()).to_biguint(), (&a + &b) % &p);
+            assert_eq!((x.clone() - y.clone()).to_biguint(), (&p + &a - &b) % &p);
+            assert_eq!((x.clone() * y.clone()).to_biguint(), (&a * &b) % &p);
+            assert_eq!((-x.clone()).to_biguint(), (&p - &a) % &p);
+
+            let mut z = x.clone();
+            z += y.clone();
+            assert_eq!(z.to_biguint(), (&a + &b) % &p);
+            let mut z = x.clone();
+            z -= &y;
+            assert_eq!(z.to_biguint(), (&p + &a - &b) % &p);
+            let mut z = x;
+            z *= y;
+            assert_eq!(z.to_biguint(), (&a * &b) % &p);
+        }
+
+        // Comparing with reduced references, so an unreduced result would not be equal
+        let n = BigUint::from(425u32);
+        for _ in 0..ROUNDS {
+            let (a, b) = (rng.gen_biguint_below(&n), rng.gen_biguint_below(&n));
+            let (x, y) = (
+                IntegersMod425::new(a.clone()),
+                IntegersMod425::new(b.clone()),
+            );
+            let reference = |el: BigUint| IntegersMod425::new(el);
+            assert_eq!(x.clone() + y.clone(), reference((&a + &b) % &n));
+            assert_eq!(x.clone() - y.clone(), reference((&n + &a - &b) % &n));
+            assert_eq!(x.clone() * y.clone(), reference((&a * &b) % &n));
+            assert_eq!(-x.clone(), reference((&n - &a) % &n));
+
+            let mut z = x.clone();
+            z += y.clone();
+            assert_eq!(z, reference((&a + &b) % &n));
+            let mut z = x.clone();
+            z -= &y;
+            assert_eq!(z, reference((&n + &a - &b) % &n));
+            let mut z = x;
+            z *= y;
+            assert_eq!(z, reference((&a * &b) % &n));
+        }
+    }
+}
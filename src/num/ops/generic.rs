@@ -30,6 +30,17 @@ pub(super) fn sub_self<const L: usize>(x: &mut [u64; L], y: &[u64; L]) -> bool {
     borrow
 }
 
+pub(super) fn mul_low<const L: usize>(x: &[u64; L], y: &[u64; L], z: &mut [u64; L]) {
+    *z = [0; L];
+    for i in 0..L {
+        let mut carry = 0u64;
+        for j in 0..(L - i) {
+            let t = (x[i] as u128) * (y[j] as u128) + (z[i + j] as u128) + (carry as u128);
+            z[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+    }
+}
 
 pub(super) fn mul<const L: usize>(x: &[u64; L], y: &[u64; L], z: &mut [u64; L + 1]) -> bool {
     false
@@ -20,3 +20,8 @@ pub(super) fn sub<const L: usize>(x: &[u64; L], y: &[u64; L], z: &mut [u64; L])
 pub(super) fn sub_self<const L: usize>(x: &mut [u64; L], y: &[u64; L]) -> bool {
     generic::sub_self(x, y)
 }
+
+/// Multiply x and y, storing the low L limbs of the product in z
+pub(super) fn mul_low<const L: usize>(x: &[u64; L], y: &[u64; L], z: &mut [u64; L]) {
+    generic::mul_low(x, y, z)
+}
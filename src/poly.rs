@@ -1,8 +1,19 @@
-use std::{collections::HashMap, iter::FromIterator};
-
-use num::Integer;
-
-use crate::{fields::Field, rings::Ring};
+pub mod ntt;
+pub mod sparse;
+pub mod symmetric;
+
+use std::{
+    collections::{HashMap, HashSet},
+    iter::FromIterator,
+};
+
+use num::{BigInt, Integer};
+
+use crate::{
+    fields::Field,
+    linalg::Matrix,
+    rings::{EuclideanDomain, Ring},
+};
 use std::fmt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,24 +21,47 @@ pub struct DensePolynomial<F> {
     coeff: Vec<F>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolyBuildError {
+    DuplicateDegree(usize),
+}
+
+impl fmt::Display for PolyBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolyBuildError::DuplicateDegree(k) => write!(f, "degree {} appears twice", k),
+        }
+    }
+}
+
+impl std::error::Error for PolyBuildError {}
+
 impl<F> DensePolynomial<F>
 where
     F: Ring,
 {
     pub fn new(it: impl IntoIterator<Item = F>) -> Self {
-        let mut coeff: Vec<_> = it.into_iter().collect();
-        // Remove trailing zeros
-        loop {
-            if coeff.is_empty() {
-                break;
-            }
-            let last = coeff.pop().unwrap();
-            if !last.is_zero() {
-                coeff.push(last);
-                break;
-            }
+        Self::from_vec_trimmed(it.into_iter().collect())
+    }
+
+    // Reuses the allocation of coeff
+    pub fn from_vec_trimmed(coeff: Vec<F>) -> Self {
+        let mut res = Self { coeff };
+        res.trim();
+        res
+    }
+
+    // Remove trailing zeros
+    fn trim(&mut self) {
+        while self.coeff.last().is_some_and(|c| c.is_zero()) {
+            self.coeff.pop();
         }
-        Self { coeff }
+    }
+
+    // Drops every term above degree, in place
+    pub fn truncate(&mut self, degree: usize) {
+        self.coeff.truncate(degree.saturating_add(1));
+        self.trim();
     }
 
     pub fn new_integers(it: impl IntoIterator<Item = impl Integer>) -> Self {
@@ -67,6 +101,21 @@ where
         Self::new(backing)
     }
 
+    // As new_degree_list, but rejects repeated degrees instead of keeping the last one
+    pub fn try_new_degree_list(
+        degree_list: impl IntoIterator<Item = (usize, F)>,
+    ) -> Result<Self, PolyBuildError> {
+        let mut seen = HashSet::new();
+        let mut terms = Vec::new();
+        for (k, v) in degree_list {
+            if !seen.insert(k) {
+                return Err(PolyBuildError::DuplicateDegree(k));
+            }
+            terms.push((k, v));
+        }
+        Ok(Self::new_degree_list(terms))
+    }
+
     // Use None to signify the zero polynomial (degree -\infty)
     pub fn degree(&self) -> Option<usize> {
         if self.coeff.len() == 0 {
@@ -122,6 +171,25 @@ where
         )
     }
 
+    // x^deg f(1 / x), the coefficients in reverse order
+    // If x^k divides f, the reverse has degree deg - k, so reversing twice gives f / x^k
+    pub fn reverse(&self) -> Self {
+        Self::new(self.coeff.iter().rev().cloned())
+    }
+
+    // Computes f(x + c), by repeated synthetic division by x - c
+    pub fn taylor_shift(&self, c: &F) -> Self {
+        let mut coeff = self.coeff.clone();
+        let n = coeff.len().saturating_sub(1);
+        for i in 0..n {
+            for j in (i..n).rev() {
+                let carry = coeff[j + 1].clone() * c;
+                coeff[j] += carry;
+            }
+        }
+        Self::new(coeff)
+    }
+
     pub fn evaluate(&self, x: F) -> F {
         if self.degree().is_none() {
             return F::zero();
@@ -136,6 +204,40 @@ where
         return b;
     }
 
+    // Computes (f(x), f'(x)) in a single Horner pass
+    pub fn evaluate_with_derivative(&self, x: &F) -> (F, F) {
+        if self.degree().is_none() {
+            return (F::zero(), F::zero());
+        }
+
+        let n = self.degree().unwrap();
+        let mut b = self.coeff(n);
+        let mut d = F::zero();
+        for i in 1..=n {
+            d = d * x + &b;
+            b = self.coeff(n - i) + b * x;
+        }
+        (b, d)
+    }
+
+    // Formal derivative
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.coeff
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, c)| c.scale(i)),
+        )
+    }
+
+    // The monic polynomial prod (x - r) over the roots, with multiplicity
+    pub fn from_roots(roots: &[F]) -> Self {
+        roots.iter().fold(Self::new([F::one()]), |acc, r| {
+            acc.mult(&Self::new([-r.clone(), F::one()]))
+        })
+    }
+
     pub fn mult(&self, other: &DensePolynomial<F>) -> Self {
         if self.is_zero() || other.is_zero() {
             return Self::zero();
@@ -143,9 +245,9 @@ where
         let n = self.degree().unwrap();
         let m = other.degree().unwrap();
 
-        let mut res = Vec::from_iter(std::iter::repeat(F::zero()).take(m + n));
-        for i in 0..n {
-            for j in 0..m {
+        let mut res = Vec::from_iter(std::iter::repeat(F::zero()).take(m + n + 1));
+        for i in 0..=n {
+            for j in 0..=m {
                 res[i + j] += self.coeff(i) * other.coeff(j);
             }
         }
@@ -158,6 +260,66 @@ impl<F> DensePolynomial<F>
 where
     F: Field,
 {
+    // Signed coefficients are reduced into [0, char) before being embedded
+    pub fn new_bigints(it: impl IntoIterator<Item = BigInt>) -> Self {
+        let characteristic = BigInt::from(F::characteristic());
+        Self::new(
+            it.into_iter()
+                .map(|c| F::integer_embed(c.mod_floor(&characteristic).to_biguint().unwrap())),
+        )
+    }
+
+    // Divides by the leading coefficient, the zero polynomial is left as is
+    pub fn normalize_monic(&self) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        let inv = self.leading().invert().unwrap();
+        Self::new(self.coeff.iter().map(|c| c.clone() * &inv))
+    }
+
+    // Monic gcd, zero only if both are zero
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let (_, r) = a.div_quotient_rem(&b);
+            a = b;
+            b = r;
+        }
+        a.normalize_monic()
+    }
+
+    // Monic gcd of all the polynomials, zero for the empty slice
+    pub fn gcd_many(polys: &[Self]) -> Self {
+        polys.iter().fold(Self::zero(), |acc, f| acc.gcd(f))
+    }
+
+    // Lagrange interpolation, the unique polynomial of degree < n through the n points
+    pub fn interpolate(points: &[F], values: &[F]) -> Self {
+        if points.len() != values.len() {
+            panic!("Expected as many values as points");
+        }
+
+        let mut res = Self::zero();
+        for (i, (x_i, y_i)) in points.iter().zip(values.iter()).enumerate() {
+            let mut basis = Self::new(vec![y_i.clone()]);
+            for (_, x_j) in points.iter().enumerate().filter(|(j, _)| *j != i) {
+                let denom = (x_i.clone() - x_j)
+                    .invert()
+                    .expect("Interpolation points must be distinct");
+                basis = basis.mult(&Self::new(vec![-x_j.clone() * &denom, denom]));
+            }
+            res = res.add(&basis);
+        }
+        res
+    }
+
+    // True iff other = c * self for some non zero c
+    pub fn eq_up_to_scalar(&self, other: &Self) -> bool {
+        self.normalize_monic() == other.normalize_monic()
+    }
+
     pub fn div_quotient_rem(&self, divisor: &DensePolynomial<F>) -> (Self, Self) {
         if divisor.is_zero() {
             panic!("Cannot reduce by the zero polynomial");
@@ -165,6 +327,9 @@ where
         if self.is_zero() {
             return (Self::zero(), Self::zero());
         }
+        if self.coeff.len() < divisor.coeff.len() {
+            return (Self::zero(), self.clone());
+        }
 
         let num_deg = self.coeff.len();
         let den_deg = divisor.coeff.len();
@@ -193,47 +358,173 @@ where
     }
 }
 
-impl<F> fmt::Display for DensePolynomial<F>
+// Evaluates sum coeffs[i] x^i, so the index is the degree. Trailing zeros are fine
+pub fn horner_eval<F: Field>(coeffs: &[F], x: &F) -> F {
+    coeffs.iter().rev().fold(F::zero(), |acc, c| acc * x + c)
+}
+
+// Interpolation through a fixed point set, given the inverse of its vandermonde matrix
+pub fn interpolate_with_inverse<F: Field>(
+    inverse_vandermonde: &Matrix<F>,
+    values: &[F],
+) -> DensePolynomial<F> {
+    DensePolynomial::new(inverse_vandermonde.mul_vec(values))
+}
+
+impl<F> EuclideanDomain for DensePolynomial<F>
+where
+    F: Field,
+{
+    // The degree, with the zero polynomial smallest
+    type Norm = Option<usize>;
+
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        self.div_quotient_rem(other)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.degree().is_none()
+    }
+
+    fn norm(&self) -> Self::Norm {
+        self.degree()
+    }
+}
+
+impl<F> DensePolynomial<F>
 where
     F: Ring + fmt::Display,
 {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // Same as Display, but starting from the constant term
+    pub fn display_ascending<'a>(&'a self) -> impl fmt::Display + 'a {
+        Ascending(self)
+    }
+
+    // Writes the non zero terms in the order of the given powers
+    fn fmt_terms(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        powers: impl Iterator<Item = usize>,
+    ) -> fmt::Result {
         if self.is_zero() {
             return write!(f, "{}", F::zero());
         }
-        let n = self.degree().unwrap();
-        for i in (0..=n).rev() {
+        let mut first = true;
+        for i in powers {
             let coeff = self.coeff(i);
-            if !coeff.is_zero() {
-                write!(
-                    f,
-                    "{}{}{}",
-                    if i == n { "" } else { " + " },
-                    if !coeff.is_one() || i == 0 {
-                        format!("{}", coeff)
-                    } else {
-                        String::new()
-                    },
-                    if i == 0 {
-                        String::new()
-                    } else if i == 1 {
-                        " x".to_string()
-                    } else {
-                        format!(" x^{}", i)
-                    }
-                )?;
+            if coeff.is_zero() {
+                continue;
+            }
+            if !first {
+                write!(f, " + ")?;
+            }
+            first = false;
+
+            let show_coeff = !coeff.is_one() || i == 0;
+            if show_coeff {
+                write!(f, "{}", coeff)?;
+            }
+            if i > 0 {
+                write!(f, "{}x", if show_coeff { " " } else { "" })?;
+            }
+            if i > 1 {
+                write!(f, "^{}", i)?;
             }
         }
         Ok(())
     }
 }
 
+struct Ascending<'a, F>(&'a DensePolynomial<F>);
+
+impl<'a, F> fmt::Display for Ascending<'a, F>
+where
+    F: Ring + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.0.degree().unwrap_or(0);
+        self.0.fmt_terms(f, 0..=n)
+    }
+}
+
+impl<F> fmt::Display for DensePolynomial<F>
+where
+    F: Ring + fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.degree().unwrap_or(0);
+        self.fmt_terms(f, (0..=n).rev())
+    }
+}
+
+// Polynomials with coefficients in $rr, which need not be a field
+#[macro_export]
+macro_rules! ring_poly_tests {
+    ($rr:ident) => {
+        paste! {
+            #[cfg(test)]
+            mod [< $rr:snake _ring_poly_tests >] {
+                use super::$rr;
+                use crate::poly::DensePolynomial;
+                use crate::rings::Ring;
+                use rand::SeedableRng;
+
+                const NUM_POLYS: usize = 100;
+                const MAX_DEGREE: usize = 8;
+
+                fn random_poly(rng: &mut impl rand::RngCore) -> DensePolynomial<$rr> {
+                    let deg = (rng.next_u32() as usize) % MAX_DEGREE;
+                    DensePolynomial::new((0..=deg).map(|_| $rr::random(rng)))
+                }
+
+                #[test]
+                fn mult_distributes_over_add() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_POLYS {
+                        let (f, g, h) = (
+                            random_poly(&mut rng),
+                            random_poly(&mut rng),
+                            random_poly(&mut rng),
+                        );
+                        assert_eq!(f.mult(&g.add(&h)), f.mult(&g).add(&f.mult(&h)));
+                        assert_eq!(f.mult(&g), g.mult(&f));
+                    }
+                }
+
+                #[test]
+                fn evaluate_is_homomorphism() {
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_POLYS {
+                        let (f, g) = (random_poly(&mut rng), random_poly(&mut rng));
+                        let x = $rr::random(&mut rng);
+                        assert_eq!(
+                            f.add(&g).evaluate(x.clone()),
+                            f.evaluate(x.clone()) + g.evaluate(x.clone())
+                        );
+                        assert_eq!(
+                            f.mult(&g).evaluate(x.clone()),
+                            f.evaluate(x.clone()) * g.evaluate(x.clone())
+                        );
+                        assert_eq!(
+                            DensePolynomial::new(vec![$rr::one()]).evaluate(x),
+                            $rr::one()
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::DensePolynomial;
+    use super::{horner_eval, interpolate_with_inverse, DensePolynomial, PolyBuildError};
     use crate::fields::primefields::PrimeField4999;
-    use crate::rings::Ring;
+    use crate::linalg::vandermonde;
+    use crate::rings::{euclidean_gcd, Ring};
+    use num::BigInt;
+    use rand::SeedableRng;
 
     #[test]
     fn basic_construction() {
@@ -277,6 +568,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bigint_construction() {
+        let p = 4999i64;
+        let f: DensePolynomial<PrimeField4999> =
+            DensePolynomial::new_bigints(vec![BigInt::from(-1)]);
+        assert_eq!(
+            f,
+            DensePolynomial::new(vec![PrimeField4999::integer_embed(p - 1)])
+        );
+
+        let big: BigInt = BigInt::from(p).pow(5u32) * 3 + 7;
+        let g: DensePolynomial<PrimeField4999> =
+            DensePolynomial::new_bigints(vec![big.clone(), -big, BigInt::from(p)]);
+        assert_eq!(g, DensePolynomial::new_integers(vec![7, -7]));
+
+        assert_eq!(
+            DensePolynomial::<PrimeField4999>::new_bigints(
+                vec![-4, 0, -2, 1].into_iter().map(BigInt::from)
+            ),
+            DensePolynomial::new_integers(vec![-4, 0, -2, 1])
+        );
+    }
+
+    #[test]
+    fn horner_eval_on_slices() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for len in 0..10 {
+            for _ in 0..10 {
+                let mut coeffs: Vec<_> =
+                    (0..len).map(|_| PrimeField4999::random(&mut rng)).collect();
+                coeffs.push(PrimeField4999::zero());
+                let x = PrimeField4999::random(&mut rng);
+                assert_eq!(
+                    horner_eval(&coeffs, &x),
+                    DensePolynomial::new(coeffs.clone()).evaluate(x)
+                );
+            }
+        }
+
+        assert!(horner_eval(&[], &PrimeField4999::one()).is_zero());
+    }
+
     #[test]
     fn shift() {
         // x^4 + 1
@@ -288,4 +621,315 @@ mod tests {
 
         assert_eq!(f.shift(3), g);
     }
+
+    #[test]
+    fn try_new_degree_list() {
+        let (a, b) = (
+            PrimeField4999::integer_embed(3),
+            PrimeField4999::integer_embed(5),
+        );
+        assert_eq!(
+            DensePolynomial::try_new_degree_list(vec![(1, a.clone()), (1, b.clone())]),
+            Err(PolyBuildError::DuplicateDegree(1))
+        );
+        assert_eq!(
+            DensePolynomial::try_new_degree_list(vec![
+                (2, a.clone()),
+                (0, b.clone()),
+                (2, b.clone())
+            ]),
+            Err(PolyBuildError::DuplicateDegree(2))
+        );
+
+        let terms = vec![(3, a.clone()), (1, b.clone()), (0, a)];
+        let f = DensePolynomial::try_new_degree_list(terms.clone()).unwrap();
+        assert_eq!(f, DensePolynomial::new_degree_list(terms));
+        assert_eq!(f, DensePolynomial::new_integers([3, 5, 0, 3]));
+    }
+
+    #[test]
+    fn from_vec_trimmed() {
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::from_vec_trimmed(
+            vec![1, 0, 0].into_iter().map(Ring::integer_embed).collect(),
+        );
+        assert_eq!(f.degree(), Some(0));
+        assert_eq!(f, DensePolynomial::new_integers([1]));
+
+        let zero: DensePolynomial<PrimeField4999> =
+            DensePolynomial::from_vec_trimmed(vec![PrimeField4999::zero(); 3]);
+        assert!(zero.is_zero());
+    }
+
+    #[test]
+    fn truncate() {
+        // 4 x^4 + 3 x^3 + x
+        let mut f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([0, 1, 0, 3, 4]);
+        f.truncate(usize::MAX);
+        assert_eq!(f.degree(), Some(4));
+        f.truncate(5);
+        assert_eq!(f.degree(), Some(4));
+        f.truncate(3);
+        assert_eq!(f, DensePolynomial::new_integers([0, 1, 0, 3]));
+        // The x^2 coefficient is zero, so this trims down to degree 1
+        f.truncate(2);
+        assert_eq!(f.degree(), Some(1));
+        assert_eq!(f, DensePolynomial::new_integers([0, 1]));
+        f.truncate(0);
+        assert!(f.is_zero());
+    }
+
+    #[test]
+    fn reverse() {
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([3, 2, 1]);
+        assert_eq!(f.reverse(), DensePolynomial::new_integers([1, 2, 3]));
+        assert_eq!(f.reverse().reverse(), f);
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let f =
+                DensePolynomial::new((0..10).map(|_| PrimeField4999::random_non_zero(&mut rng)));
+            assert_eq!(f.reverse().reverse(), f);
+            assert_eq!(f.reverse().degree(), f.degree());
+        }
+
+        // x^2 (x + 2) reverses to 2 x + 1, losing the power of x
+        let g: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([0, 0, 2, 1]);
+        assert_eq!(g.reverse(), DensePolynomial::new_integers([1, 2]));
+        assert_eq!(g.reverse().reverse(), DensePolynomial::new_integers([2, 1]));
+
+        assert!(DensePolynomial::<PrimeField4999>::zero()
+            .reverse()
+            .is_zero());
+    }
+
+    #[test]
+    fn gcd_many() {
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([2, -3, 1]);
+        let g: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([3, -4, 1]);
+        let expected = DensePolynomial::new_integers([-1, 1]);
+        assert_eq!(DensePolynomial::gcd_many(&[f.clone(), g.clone()]), expected);
+
+        let scaled = g.mult(&DensePolynomial::new_integers([5]));
+        assert_eq!(DensePolynomial::gcd_many(&[f.clone(), scaled]), expected);
+        assert_eq!(DensePolynomial::gcd_many(std::slice::from_ref(&f)), f);
+        assert!(DensePolynomial::<PrimeField4999>::gcd_many(&[]).is_zero());
+    }
+
+    #[test]
+    fn display() {
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([1, 2, 1]);
+        assert_eq!(f.to_string(), "x^2 + 2 x + 1");
+        assert_eq!(f.display_ascending().to_string(), "1 + 2 x + x^2");
+
+        let g: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([0, 1, 0, 3]);
+        assert_eq!(g.to_string(), "3 x^3 + x");
+        assert_eq!(g.display_ascending().to_string(), "x + 3 x^3");
+
+        let zero: DensePolynomial<PrimeField4999> = DensePolynomial::zero();
+        assert_eq!(zero.to_string(), "0");
+        assert_eq!(zero.display_ascending().to_string(), "0");
+    }
+
+    #[test]
+    fn taylor_shift() {
+        // (x + 1)^2 + 1 = x^2 + 2 x + 2
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 0, 1]);
+        assert_eq!(
+            f.taylor_shift(&PrimeField4999::one()),
+            DensePolynomial::new_integers(vec![2, 2, 1])
+        );
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for deg in 0..10 {
+            let f: DensePolynomial<PrimeField4999> =
+                DensePolynomial::new((0..=deg).map(|_| PrimeField4999::random(&mut rng)));
+            let c = PrimeField4999::random(&mut rng);
+            let shifted = f.taylor_shift(&c);
+            assert_eq!(shifted.taylor_shift(&-c.clone()), f);
+
+            for _ in 0..10 {
+                let t = PrimeField4999::random(&mut rng);
+                assert_eq!(shifted.evaluate(t.clone()), f.evaluate(t + c.clone()));
+            }
+        }
+
+        assert!(DensePolynomial::<PrimeField4999>::zero()
+            .taylor_shift(&PrimeField4999::one())
+            .is_zero());
+    }
+
+    #[test]
+    fn derivative() {
+        // x^4 + 3 x^2 + 2 x + 1
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 2, 3, 0, 1]);
+        assert_eq!(
+            f.derivative(),
+            DensePolynomial::new_integers(vec![2, 6, 0, 4])
+        );
+        assert!(DensePolynomial::<PrimeField4999>::new_integers(vec![5])
+            .derivative()
+            .is_zero());
+        assert!(DensePolynomial::<PrimeField4999>::zero()
+            .derivative()
+            .is_zero());
+    }
+
+    #[test]
+    fn evaluate_with_derivative() {
+        const ROUNDS: usize = 100;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for deg in 0..10 {
+            for _ in 0..ROUNDS {
+                let f: DensePolynomial<PrimeField4999> =
+                    DensePolynomial::new((0..=deg).map(|_| PrimeField4999::random(&mut rng)));
+                let x = PrimeField4999::random(&mut rng);
+                assert_eq!(
+                    f.evaluate_with_derivative(&x),
+                    (f.evaluate(x.clone()), f.derivative().evaluate(x))
+                );
+            }
+        }
+
+        let zero = DensePolynomial::<PrimeField4999>::zero();
+        assert_eq!(
+            zero.evaluate_with_derivative(&PrimeField4999::one()),
+            (PrimeField4999::zero(), PrimeField4999::zero())
+        );
+
+        // (x - 1)(x - 2), simple roots
+        let f = DensePolynomial::new_integers(vec![2, -3, 1]);
+        assert_eq!(
+            f.evaluate_with_derivative(&PrimeField4999::one()),
+            (PrimeField4999::zero(), -PrimeField4999::one())
+        );
+        assert_eq!(
+            f.evaluate_with_derivative(&PrimeField4999::integer_embed(2)),
+            (PrimeField4999::zero(), PrimeField4999::one())
+        );
+
+        // (x - 1)^2, double root
+        let g = DensePolynomial::new_integers(vec![1, -2, 1]);
+        assert_eq!(
+            g.evaluate_with_derivative(&PrimeField4999::one()),
+            (PrimeField4999::zero(), PrimeField4999::zero())
+        );
+    }
+
+    #[test]
+    fn eq_up_to_scalar() {
+        // x + 1 and 2 x + 2
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 1]);
+        let g = DensePolynomial::new_integers(vec![2, 2]);
+        assert_ne!(f, g);
+        assert!(f.eq_up_to_scalar(&g));
+        assert!(g.eq_up_to_scalar(&f));
+        assert_eq!(f.normalize_monic(), g.normalize_monic());
+        assert_eq!(g.normalize_monic(), f);
+
+        // x + 2 is not a multiple of x + 1
+        let h = DensePolynomial::new_integers(vec![2, 1]);
+        assert!(!f.eq_up_to_scalar(&h));
+
+        let zero = DensePolynomial::zero();
+        assert!(zero.eq_up_to_scalar(&zero));
+        assert!(!zero.eq_up_to_scalar(&f));
+        assert!(!f.eq_up_to_scalar(&zero));
+        assert!(zero.normalize_monic().is_zero());
+
+        let constant: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![42]);
+        assert_eq!(
+            constant.normalize_monic(),
+            DensePolynomial::new_integers(vec![1])
+        );
+    }
+
+    #[test]
+    fn gcd() {
+        // (x - 1)(x - 2) and (x - 1)(x - 3)
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![2, -3, 1]);
+        let g = DensePolynomial::new_integers(vec![3, -4, 1]);
+        let expected = DensePolynomial::new_integers(vec![-1, 1]);
+        assert_eq!(f.gcd(&g), expected);
+        assert!(euclidean_gcd(f.clone(), g.clone()).eq_up_to_scalar(&expected));
+
+        // Scaling does not change the gcd
+        let h = DensePolynomial::new_integers(vec![6, -8, 2]);
+        assert_eq!(f.gcd(&h), expected);
+
+        // (x - 1)(x - 2) and x^2 + 1 are coprime
+        let k = DensePolynomial::new_integers(vec![1, 0, 1]);
+        assert_eq!(f.gcd(&k), DensePolynomial::new_integers(vec![1]));
+        assert_eq!(euclidean_gcd(f.clone(), k).degree(), Some(0));
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let a: DensePolynomial<PrimeField4999> =
+                DensePolynomial::new((0..5).map(|_| PrimeField4999::random(&mut rng)));
+            let b = DensePolynomial::new((0..4).map(|_| PrimeField4999::random(&mut rng)));
+            assert!(euclidean_gcd(a.clone(), b.clone()).eq_up_to_scalar(&a.gcd(&b)));
+        }
+
+        let zero = DensePolynomial::zero();
+        assert_eq!(f.gcd(&zero), f);
+        assert_eq!(zero.gcd(&f), f);
+        assert!(zero.gcd(&zero).is_zero());
+    }
+
+    #[test]
+    fn gcd_lower_degree_first() {
+        // x + 1 and x^3 are coprime
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 1]);
+        let g = DensePolynomial::new_integers(vec![0, 0, 0, 1]);
+        assert_eq!(f.gcd(&g), DensePolynomial::new_integers(vec![1]));
+
+        // x - 1 divides (x - 1)(x - 2)
+        let h: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![-1, 1]);
+        let k = DensePolynomial::new_integers(vec![2, -3, 1]);
+        assert_eq!(h.gcd(&k), h);
+        assert_eq!(h.gcd(&k), k.gcd(&h));
+    }
+
+    #[test]
+    fn division_by_larger_degree() {
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers([1, 2]);
+        let g = DensePolynomial::new_integers([3, 0, 1]);
+        assert_eq!(f.div_quotient_rem(&g), (DensePolynomial::zero(), f.clone()));
+        assert_eq!(f.gcd(&g), g.gcd(&f));
+    }
+
+    #[test]
+    fn mult() {
+        // (x + 1)(x - 1) = x^2 - 1
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 1]);
+        let g = DensePolynomial::new_integers(vec![-1, 1]);
+        assert_eq!(f.mult(&g), DensePolynomial::new_integers(vec![-1, 0, 1]));
+
+        let c: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![3]);
+        assert_eq!(c.mult(&c), DensePolynomial::new_integers(vec![9]));
+    }
+
+    #[test]
+    fn interpolation() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in 1..10 {
+            let points: Vec<_> = (0..n)
+                .map(|i| PrimeField4999::integer_embed(3 * i + 1))
+                .collect();
+            let f: DensePolynomial<PrimeField4999> =
+                DensePolynomial::new((0..n).map(|_| PrimeField4999::random(&mut rng)));
+            let values: Vec<_> = points.iter().map(|x| f.evaluate(x.clone())).collect();
+
+            let lagrange = DensePolynomial::interpolate(&points, &values);
+            assert_eq!(lagrange, f);
+
+            let inverse = vandermonde(&points).invert().unwrap();
+            assert_eq!(interpolate_with_inverse(&inverse, &values), lagrange);
+
+            // The inverse can be reused for other values
+            let g: DensePolynomial<PrimeField4999> =
+                DensePolynomial::new((0..n).map(|_| PrimeField4999::random(&mut rng)));
+            let values: Vec<_> = points.iter().map(|x| g.evaluate(x.clone())).collect();
+            assert_eq!(interpolate_with_inverse(&inverse, &values), g);
+        }
+    }
 }
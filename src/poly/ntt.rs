@@ -0,0 +1,243 @@
+use num::BigUint;
+
+use crate::{fields::Field, poly::DensePolynomial, primes::rewrite_n};
+
+// Evaluation domain of the size-th roots of unity, size a power of two
+#[derive(Debug, Clone)]
+pub struct NttDomain<F> {
+    size: usize,
+    // A primitive size-th root of unity
+    omega: F,
+    // omega^i and omega^-i for i < size / 2
+    twiddles: Vec<F>,
+    inverse_twiddles: Vec<F>,
+    size_inv: F,
+}
+
+impl<F> NttDomain<F>
+where
+    F: Field,
+{
+    // None if the field has no primitive size-th root of unity
+    pub fn new(size: usize) -> Option<Self> {
+        if !size.is_power_of_two() {
+            panic!("NTT size must be a power of two");
+        }
+
+        // order - 1 = 2^s t, and a non residue to the t has order 2^s
+        let (s, t) = rewrite_n(F::order());
+        if size > 1 << s.min(usize::BITS as usize - 1) {
+            return None;
+        }
+
+        let mut z = F::one();
+        loop {
+            z += F::one();
+            if z.is_zero() {
+                panic!("No non residue in the prime subfield");
+            }
+            if z.legendre() == -1 {
+                break;
+            }
+        }
+        let omega = z
+            .pow_biguint(&t)
+            .pow_biguint(&(BigUint::from(1u8) << (s - size.trailing_zeros() as usize)));
+        let omega_inv = omega.invert().unwrap();
+
+        let powers = |base: &F| {
+            let mut acc = F::one();
+            let mut res = Vec::with_capacity(size / 2);
+            for _ in 0..size / 2 {
+                res.push(acc.clone());
+                acc *= base.clone();
+            }
+            res
+        };
+
+        Some(NttDomain {
+            size,
+            omega: omega.clone(),
+            twiddles: powers(&omega),
+            inverse_twiddles: powers(&omega_inv),
+            size_inv: F::integer_embed(size).invert().unwrap(),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn omega(&self) -> &F {
+        &self.omega
+    }
+
+    // Replaces the coefficients by the evaluations at omega^i
+    pub fn forward(&self, values: &mut [F]) {
+        self.transform(values, &self.twiddles);
+    }
+
+    pub fn inverse(&self, values: &mut [F]) {
+        self.transform(values, &self.inverse_twiddles);
+        for v in values.iter_mut() {
+            *v *= self.size_inv.clone();
+        }
+    }
+
+    // Iterative radix 2 Cooley-Tukey
+    fn transform(&self, values: &mut [F], twiddles: &[F]) {
+        let n = self.size;
+        if values.len() != n {
+            panic!("Expected {} values, got {}", n, values.len());
+        }
+
+        // Bit reversal permutation
+        if n > 1 {
+            let shift = usize::BITS - n.trailing_zeros();
+            for i in 0..n {
+                let j = i.reverse_bits() >> shift;
+                if i < j {
+                    values.swap(i, j);
+                }
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let step = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..len / 2 {
+                    let u = values[start + k].clone();
+                    let v = values[start + k + len / 2].clone() * &twiddles[k * step];
+                    values[start + k] = u.clone() + &v;
+                    values[start + k + len / 2] = u - v;
+                }
+            }
+            len *= 2;
+        }
+    }
+}
+
+impl<F> DensePolynomial<F>
+where
+    F: Field,
+{
+    // Multiplication through the NTT, the domain can be reused across calls
+    // Panics if the given domain is too small, or if no suitable domain exists
+    pub fn mult_ntt(&self, other: &Self, domain: Option<&NttDomain<F>>) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return Self::zero();
+        }
+
+        let len = self.coeff.len() + other.coeff.len() - 1;
+        let fresh;
+        let domain = match domain {
+            Some(domain) => domain,
+            None => {
+                fresh = NttDomain::new(len.next_power_of_two())
+                    .expect("No root of unity of the required order");
+                &fresh
+            }
+        };
+        if domain.size() < len {
+            panic!(
+                "Domain of size {} too small for {} coefficients",
+                domain.size(),
+                len
+            );
+        }
+
+        let pad = |coeff: &[F]| {
+            let mut res = coeff.to_vec();
+            res.resize(domain.size(), F::zero());
+            res
+        };
+        let (mut a, mut b) = (pad(&self.coeff), pad(&other.coeff));
+        domain.forward(&mut a);
+        domain.forward(&mut b);
+        for (x, y) in a.iter_mut().zip(b) {
+            *x *= y;
+        }
+        domain.inverse(&mut a);
+        Self::new(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NttDomain;
+    use crate::fields::primefields::{NttField65537, PrimeField4999};
+    use crate::fields::Field;
+    use crate::poly::DensePolynomial;
+    use crate::rings::Ring;
+    use rand::SeedableRng;
+
+    #[test]
+    fn domain_sizes() {
+        // 4999 - 1 = 2 * 2499
+        assert!(NttDomain::<PrimeField4999>::new(1).is_some());
+        assert!(NttDomain::<PrimeField4999>::new(2).is_some());
+        assert!(NttDomain::<PrimeField4999>::new(4).is_none());
+        assert!(NttDomain::<NttField65537>::new(1 << 16).is_some());
+        assert!(NttDomain::<NttField65537>::new(1 << 17).is_none());
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let domain = NttDomain::<PrimeField4999>::new(2).unwrap();
+        let v: Vec<_> = (0..2).map(|_| PrimeField4999::random(&mut rng)).collect();
+        let mut w = v.clone();
+        domain.forward(&mut w);
+        assert_eq!(w, vec![v[0].clone() + &v[1], v[0].clone() - &v[1]]);
+        domain.inverse(&mut w);
+        assert_eq!(w, v);
+
+        for log_size in 0..10 {
+            let domain = NttDomain::<NttField65537>::new(1 << log_size).unwrap();
+            let v: Vec<_> = (0..1 << log_size)
+                .map(|_| NttField65537::random(&mut rng))
+                .collect();
+            let mut w = v.clone();
+            domain.forward(&mut w);
+
+            // The transform is evaluation at the powers of omega
+            let f = DensePolynomial::new(v.clone());
+            let omega = domain.omega().clone();
+            assert!(omega.pow(1u32 << log_size).is_one());
+            if log_size > 0 {
+                assert!(!omega.pow(1u32 << (log_size - 1)).is_one());
+            }
+            let mut x = NttField65537::one();
+            for w_i in &w {
+                assert_eq!(*w_i, f.evaluate(x.clone()));
+                x *= omega.clone();
+            }
+
+            domain.inverse(&mut w);
+            assert_eq!(w, v);
+        }
+    }
+
+    #[test]
+    fn mult_ntt() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let domain = NttDomain::<NttField65537>::new(64).unwrap();
+        for deg in 0..32 {
+            let f: DensePolynomial<NttField65537> =
+                DensePolynomial::new((0..=deg).map(|_| NttField65537::random(&mut rng)));
+            let g = DensePolynomial::new((0..=(31 - deg)).map(|_| NttField65537::random(&mut rng)));
+            let expected = f.mult(&g);
+            assert_eq!(f.mult_ntt(&g, None), expected);
+            assert_eq!(f.mult_ntt(&g, Some(&domain)), expected);
+        }
+
+        // A product with two coefficients only needs a domain of size 2
+        let f: DensePolynomial<PrimeField4999> = DensePolynomial::new_integers(vec![1, 1]);
+        let g = DensePolynomial::new_integers(vec![-3]);
+        let domain = NttDomain::new(2).unwrap();
+        assert_eq!(f.mult_ntt(&g, Some(&domain)), f.mult(&g));
+        assert_eq!(f.mult_ntt(&g, None), f.mult(&g));
+        assert!(f.mult_ntt(&DensePolynomial::zero(), None).is_zero());
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+
+use num::Integer;
+
+use crate::{poly::DensePolynomial, rings::Ring};
+
+// Polynomial stored as a map from degree to non zero coefficient,
+// suited to high degree polynomials with few terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparsePolynomial<F> {
+    terms: BTreeMap<usize, F>,
+}
+
+impl<F> SparsePolynomial<F>
+where
+    F: Ring,
+{
+    // Repeated degrees are summed
+    pub fn new(degree_list: impl IntoIterator<Item = (usize, F)>) -> Self {
+        let mut terms = BTreeMap::new();
+        for (k, v) in degree_list {
+            *terms.entry(k).or_insert_with(F::zero) += v;
+        }
+        terms.retain(|_, v: &mut F| !v.is_zero());
+        Self { terms }
+    }
+
+    pub fn new_integers(degree_list: impl IntoIterator<Item = (usize, impl Integer)>) -> Self {
+        Self::new(
+            degree_list
+                .into_iter()
+                .map(|(k, v)| (k, F::integer_embed(v))),
+        )
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            terms: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    // Use None to signify the zero polynomial (degree -\infty)
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.keys().next_back().copied()
+    }
+
+    pub fn coeff(&self, pos: usize) -> F {
+        self.terms.get(&pos).cloned().unwrap_or_else(F::zero)
+    }
+
+    // Number of non zero terms
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    // Horner over the non zero terms, jumping the gaps with pow
+    pub fn evaluate(&self, x: &F) -> F {
+        let mut it = self.terms.iter().rev();
+        let (mut prev, mut b) = match it.next() {
+            None => return F::zero(),
+            Some((k, v)) => (*k, v.clone()),
+        };
+
+        for (k, v) in it {
+            b = b * &x.positive_pow(prev - k) + v;
+            prev = *k;
+        }
+        b * &x.positive_pow(prev)
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        Self::new(
+            self.terms
+                .iter()
+                .chain(other.terms.iter())
+                .map(|(k, v)| (*k, v.clone())),
+        )
+    }
+
+    pub fn mult(&self, other: &Self) -> Self {
+        Self::new(
+            self.terms
+                .iter()
+                .flat_map(|(i, a)| other.terms.iter().map(move |(j, b)| (i + j, a.clone() * b))),
+        )
+    }
+
+    pub fn to_dense(&self) -> DensePolynomial<F> {
+        let mut backing = vec![F::zero(); self.degree().map_or(0, |d| d + 1)];
+        for (k, v) in self.terms.iter() {
+            backing[*k] = v.clone();
+        }
+        DensePolynomial::new(backing)
+    }
+
+    pub fn from_dense(f: &DensePolynomial<F>) -> Self {
+        Self::new(f.coeff.iter().cloned().enumerate())
+    }
+}
+
+impl<F: Ring> From<&DensePolynomial<F>> for SparsePolynomial<F> {
+    fn from(f: &DensePolynomial<F>) -> Self {
+        Self::from_dense(f)
+    }
+}
+
+impl<F: Ring> From<&SparsePolynomial<F>> for DensePolynomial<F> {
+    fn from(f: &SparsePolynomial<F>) -> Self {
+        f.to_dense()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparsePolynomial;
+    use crate::fields::primefields::PrimeField4999;
+    use crate::poly::DensePolynomial;
+    use crate::rings::Ring;
+    use rand::SeedableRng;
+
+    #[test]
+    fn basic_construction() {
+        let zero: SparsePolynomial<PrimeField4999> = SparsePolynomial::zero();
+        assert!(zero.is_zero());
+        assert!(zero.degree().is_none());
+        assert!(zero.coeff(42).is_zero());
+
+        // Zeros are dropped, repeated degrees summed
+        let f: SparsePolynomial<PrimeField4999> =
+            SparsePolynomial::new_integers(vec![(0, 1), (3, 0), (1000, 2), (1000, -1)]);
+        assert_eq!(f.degree(), Some(1000));
+        assert_eq!(f.num_terms(), 2);
+        assert_eq!(f.coeff(1000), PrimeField4999::one());
+
+        let constant: SparsePolynomial<PrimeField4999> =
+            SparsePolynomial::new_integers(vec![(0, 5)]);
+        assert_eq!(constant.degree(), Some(0));
+    }
+
+    #[test]
+    fn evaluation() {
+        // x^1000 + 1
+        let f: SparsePolynomial<PrimeField4999> =
+            SparsePolynomial::new_integers(vec![(0, 1), (1000, 1)]);
+        let dense = f.to_dense();
+        assert_eq!(dense.degree(), Some(1000));
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..100 {
+            let x = PrimeField4999::random(&mut rng);
+            assert_eq!(f.evaluate(&x), dense.evaluate(x.clone()));
+            assert_eq!(
+                f.evaluate(&x),
+                x.positive_pow(1000u32) + PrimeField4999::one()
+            );
+        }
+
+        let zero = SparsePolynomial::<PrimeField4999>::zero();
+        assert!(zero.evaluate(&PrimeField4999::one()).is_zero());
+    }
+
+    #[test]
+    fn arithmetic() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let f: SparsePolynomial<PrimeField4999> =
+            SparsePolynomial::new_integers(vec![(0, 3), (17, 1), (500, -2)]);
+        let g = SparsePolynomial::new_integers(vec![(1, 4), (17, -1), (300, 7)]);
+        let sum = f.add(&g);
+        let prod = f.mult(&g);
+        assert_eq!(sum.coeff(17), PrimeField4999::zero());
+        assert_eq!(prod.degree(), Some(800));
+
+        for _ in 0..100 {
+            let x = PrimeField4999::random(&mut rng);
+            assert_eq!(sum.evaluate(&x), f.evaluate(&x) + g.evaluate(&x));
+            assert_eq!(prod.evaluate(&x), f.evaluate(&x) * g.evaluate(&x));
+        }
+
+        assert!(f.mult(&SparsePolynomial::zero()).is_zero());
+        assert_eq!(f.add(&SparsePolynomial::zero()), f);
+    }
+
+    #[test]
+    fn conversions() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for deg in 0..20 {
+            let f: DensePolynomial<PrimeField4999> =
+                DensePolynomial::new((0..=deg).map(|_| PrimeField4999::random(&mut rng)));
+            let sparse = SparsePolynomial::from(&f);
+            assert_eq!(DensePolynomial::from(&sparse), f);
+            assert_eq!(sparse.degree(), f.degree());
+        }
+
+        let f: SparsePolynomial<PrimeField4999> =
+            SparsePolynomial::new_integers(vec![(0, 1), (1000, 1)]);
+        assert_eq!(SparsePolynomial::from_dense(&f.to_dense()), f);
+
+        let zero = SparsePolynomial::<PrimeField4999>::zero();
+        assert!(zero.to_dense().is_zero());
+        assert!(SparsePolynomial::from_dense(&DensePolynomial::<PrimeField4999>::zero()).is_zero());
+    }
+}
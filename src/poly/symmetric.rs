@@ -0,0 +1,128 @@
+// Newton's identities, relating the power sums p_k = sum x_i^k of n values
+// to their elementary symmetric polynomials e_k, via
+// k e_k = sum_{i = 1}^{k} (-1)^(i - 1) e_(k - i) p_i
+// Both directions divide or multiply by k, so the characteristic must exceed the number of terms
+use crate::fields::Field;
+
+// Maps [p_1, ..., p_n] to [e_1, ..., e_n]
+// Panics if some k <= n is zero in the field
+pub fn power_sums_to_elementary<F: Field>(power_sums: &[F]) -> Vec<F> {
+    let mut elementary = vec![F::one()];
+    for k in 1..=power_sums.len() {
+        let mut acc = F::zero();
+        for i in 1..=k {
+            let term = elementary[k - i].clone() * &power_sums[i - 1];
+            acc = if i % 2 == 1 { acc + term } else { acc - term };
+        }
+        let k_inv = F::integer_embed(k)
+            .invert()
+            .expect("Characteristic must exceed the number of power sums");
+        elementary.push(acc * k_inv);
+    }
+    elementary.split_off(1)
+}
+
+// Maps [e_1, ..., e_n] to [p_1, ..., p_n]
+pub fn elementary_to_power_sums<F: Field>(elementary: &[F]) -> Vec<F> {
+    let e = |k: usize| {
+        if k == 0 {
+            F::one()
+        } else {
+            elementary[k - 1].clone()
+        }
+    };
+
+    let mut power_sums: Vec<F> = Vec::with_capacity(elementary.len());
+    for k in 1..=elementary.len() {
+        // p_k = (-1)^(k - 1) k e_k + sum_{i = 1}^{k - 1} (-1)^(k - 1 + i) e_(k - i) p_i
+        let mut acc = e(k).scale(k);
+        if k % 2 == 0 {
+            acc = -acc;
+        }
+        for i in 1..k {
+            let term = e(k - i) * &power_sums[i - 1];
+            acc = if (k - 1 + i) % 2 == 0 {
+                acc + term
+            } else {
+                acc - term
+            };
+        }
+        power_sums.push(acc);
+    }
+    power_sums
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::{elementary_to_power_sums, power_sums_to_elementary};
+    use crate::fields::{primefields::PrimeField4999, Field};
+    use crate::poly::DensePolynomial;
+    use crate::rings::Ring;
+
+    fn power_sums(roots: &[PrimeField4999], n: usize) -> Vec<PrimeField4999> {
+        (1..=n)
+            .map(|k| {
+                roots
+                    .iter()
+                    .fold(PrimeField4999::zero(), |acc, r| acc + r.pow(k))
+            })
+            .collect()
+    }
+
+    // e_k is (-1)^k times the coefficient of x^(n - k) in prod (x - r)
+    fn elementary_from_poly(f: &DensePolynomial<PrimeField4999>) -> Vec<PrimeField4999> {
+        let n = f.degree().unwrap();
+        (1..=n)
+            .map(|k| {
+                let c = f.coeff(n - k);
+                if k % 2 == 0 {
+                    c
+                } else {
+                    -c
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn small_roots() {
+        let roots: Vec<_> = [1, 2, 3]
+            .iter()
+            .map(|r| PrimeField4999::integer_embed(*r))
+            .collect();
+        let p = power_sums(&roots, 3);
+        assert_eq!(
+            p,
+            [6, 14, 36]
+                .iter()
+                .map(|x| PrimeField4999::integer_embed(*x))
+                .collect::<Vec<_>>()
+        );
+
+        let e = power_sums_to_elementary(&p);
+        let f = DensePolynomial::from_roots(&roots);
+        assert_eq!(f, DensePolynomial::new_integers([-6, 11, -6, 1]));
+        assert_eq!(e, elementary_from_poly(&f));
+        assert_eq!(elementary_to_power_sums(&e), p);
+
+        assert!(power_sums_to_elementary::<PrimeField4999>(&[]).is_empty());
+        assert!(elementary_to_power_sums::<PrimeField4999>(&[]).is_empty());
+    }
+
+    #[test]
+    fn random_roots() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for n in 1..20 {
+            let roots: Vec<_> = (0..n).map(|_| PrimeField4999::random(&mut rng)).collect();
+            let p = power_sums(&roots, n);
+            let e = power_sums_to_elementary(&p);
+            assert_eq!(
+                e,
+                elementary_from_poly(&DensePolynomial::from_roots(&roots))
+            );
+            assert_eq!(elementary_to_power_sums(&e), p);
+        }
+    }
+}
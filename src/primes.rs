@@ -1,6 +1,13 @@
-use num::{bigint::RandBigInt, range, BigUint, Integer, One, Zero};
+use std::fmt;
+
+use num::{
+    bigint::RandBigInt, integer::Roots, range, BigInt, BigUint, Integer, One, ToPrimitive, Zero,
+};
 
 use contracts::*;
+use lazy_static::lazy_static;
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
 
 // Write n = 2^s * d + 1, returns (s, d)
 // Assumes that s < 2^64
@@ -20,22 +27,240 @@ pub fn rewrite_n<I: Integer>(n: I) -> (usize, I) {
     (s, d)
 }
 
-fn state_setup(n: BigUint) -> Result<InnerRabinState, MillerRabinResult> {
-    if n == BigUint::zero() {
-        return Err(MillerRabinResult::Zero);
+// Sieve of Eratosthenes, returns all primes strictly less than bound
+pub fn sieve(bound: u64) -> Vec<u64> {
+    if bound < 2 {
+        return Vec::new();
+    }
+
+    let bound = bound as usize;
+    let mut is_prime = vec![true; bound];
+    is_prime[0] = false;
+    is_prime[1] = false;
+
+    let mut i = 2;
+    while i * i < bound {
+        if is_prime[i] {
+            for j in (i * i..bound).step_by(i) {
+                is_prime[j] = false;
+            }
+        }
+        i += 1;
+    }
+
+    is_prime
+        .into_iter()
+        .enumerate()
+        .filter(|(_, p)| *p)
+        .map(|(i, _)| i as u64)
+        .collect()
+}
+
+const SIEVE_SEGMENT: u64 = 1 << 16;
+// Beyond this the base primes up to sqrt(hi) get too expensive to sieve
+const SEGMENTED_SIEVE_BOUND: u64 = 1 << 40;
+
+// All primes in [lo, hi), with a segmented sieve for small enough hi and Miller-Rabin otherwise
+pub fn primes_in_range(lo: &BigUint, hi: &BigUint) -> Vec<BigUint> {
+    if lo >= hi {
+        return Vec::new();
+    }
+
+    match hi.to_u64() {
+        Some(hi) if hi <= SEGMENTED_SIEVE_BOUND => segmented_sieve(lo.to_u64().unwrap(), hi)
+            .into_iter()
+            .map(BigUint::from)
+            .collect(),
+        _ => range(lo.clone(), hi.clone())
+            .filter(is_probable_prime)
+            .collect(),
+    }
+}
+
+fn segmented_sieve(lo: u64, hi: u64) -> Vec<u64> {
+    let lo = lo.max(2);
+    let base_primes = sieve(Roots::sqrt(&hi) + 1);
+
+    let mut primes = Vec::new();
+    let mut start = lo;
+    while start < hi {
+        let end = (start + SIEVE_SEGMENT).min(hi);
+        let mut is_prime = vec![true; (end - start) as usize];
+        for &p in base_primes.iter().take_while(|&&p| p * p < end) {
+            let first = (p * p).max(start.div_ceil(p) * p);
+            for j in (first..end).step_by(p as usize) {
+                is_prime[(j - start) as usize] = false;
+            }
+        }
+
+        primes.extend(
+            is_prime
+                .into_iter()
+                .enumerate()
+                .filter(|(_, p)| *p)
+                .map(|(i, _)| start + i as u64),
+        );
+        start = end;
+    }
+    primes
+}
+
+// Legendre symbol (a / p) as a^((p - 1) / 2) mod p, assumes p is an odd prime
+pub fn euler_criterion(a: &BigUint, p: &BigUint) -> i8 {
+    let res = a.modpow(&((p - 1u8) >> 1), p);
+    if res.is_zero() {
+        0
+    } else if res.is_one() {
+        1
+    } else {
+        debug_assert_eq!(res, p - 1u8);
+        -1
+    }
+}
+
+// Primes used to screen candidates before running any witness
+const WHEEL_BOUND: u64 = 2000;
+
+lazy_static! {
+    static ref WHEEL_PRIMES: Vec<u64> = sieve(WHEEL_BOUND);
+}
+
+// The smallest prime factor of n below WHEEL_BOUND, if any, unless n is that prime
+pub fn quick_composite_check(n: &BigUint) -> Option<BigUint> {
+    WHEEL_PRIMES
+        .iter()
+        .map(|p| BigUint::from(*p))
+        .take_while(|p| p < n)
+        .find(|p| n.is_multiple_of(p))
+}
+
+// Computes (U_k mod n, V_k mod n) for the Lucas sequences with parameters (P, Q)
+// U_0 = 0, U_1 = 1, V_0 = 2, V_1 = P, and X_{k+1} = P X_k - Q X_{k-1}
+// We run a ladder on (U_k, U_{k+1}) which avoids dividing by two, so any n works
+pub fn lucas_uv(p: &BigInt, q: &BigInt, k: &BigUint, n: &BigUint) -> (BigUint, BigUint) {
+    if n.is_zero() {
+        panic!("Zero modulus not allowed");
+    }
+
+    let n = BigInt::from(n.clone());
+    let p = p.mod_floor(&n);
+    let q = q.mod_floor(&n);
+    let two = BigInt::from(2u8);
+
+    let mut u_k = BigInt::zero();
+    let mut u_k_1 = BigInt::one() % &n;
+
+    for i in (0..k.bits()).rev() {
+        // U_{2k} = U_k (2 U_{k+1} - P U_k)
+        // U_{2k+1} = U_{k+1}^2 - Q U_k^2
+        // U_{2k+2} = U_{k+1} (P U_{k+1} - 2 Q U_k)
+        let u_2k = (&u_k * (&two * &u_k_1 - &p * &u_k)).mod_floor(&n);
+        let u_2k_1 = (&u_k_1 * &u_k_1 - &q * &u_k * &u_k).mod_floor(&n);
+        if k.bit(i) {
+            let u_2k_2 = (&u_k_1 * (&p * &u_k_1 - &two * &q * &u_k)).mod_floor(&n);
+            u_k = u_2k_1;
+            u_k_1 = u_2k_2;
+        } else {
+            u_k = u_2k;
+            u_k_1 = u_2k_1;
+        }
     }
-    if n == BigUint::one() {
-        return Err(MillerRabinResult::One);
+
+    // V_k = 2 U_{k+1} - P U_k
+    let v_k = (two * &u_k_1 - &p * &u_k).mod_floor(&n);
+    (u_k.to_biguint().unwrap(), v_k.to_biguint().unwrap())
+}
+
+const PRIMES_ITERATOR_ROUNDS: usize = 64;
+
+// Yields 2, 3, 5, ... testing candidates one at a time with Miller-Rabin
+#[derive(Debug, Clone)]
+pub struct Primes {
+    next: BigUint,
+    rng: ChaCha20Rng,
+}
+
+impl Primes {
+    pub fn new() -> Self {
+        Self::with_seed(0)
     }
-    if n == BigUint::from(2u8) {
-        return Err(MillerRabinResult::CertainPrime);
+
+    pub fn with_seed(seed: u64) -> Self {
+        Primes {
+            next: BigUint::from(2u8),
+            rng: ChaCha20Rng::seed_from_u64(seed),
+        }
     }
+}
 
-    if n.is_even() {
-        return Err(MillerRabinResult::CompositeEven);
+impl Default for Primes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for Primes {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == BigUint::from(2u8) {
+            self.next = BigUint::from(3u8);
+            return Some(BigUint::from(2u8));
+        }
+
+        loop {
+            let candidate = self.next.clone();
+            self.next += 2u8;
+            if miller_rabin_with_randomness(
+                &mut self.rng,
+                candidate.clone(),
+                PRIMES_ITERATOR_ROUNDS,
+            )
+            .is_prime()
+            {
+                return Some(candidate);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrivialInput {
+    Zero,
+    One,
+}
+
+// What can be said about n before running any witness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrimalityClass {
+    Trivial(TrivialInput),
+    DefinitelyPrime,
+    DefinitelyComposite,
+    NeedsWitnessTest,
+}
+
+pub fn classify(n: &BigUint) -> PrimalityClass {
+    if n.is_zero() {
+        PrimalityClass::Trivial(TrivialInput::Zero)
+    } else if n.is_one() {
+        PrimalityClass::Trivial(TrivialInput::One)
+    } else if n == &BigUint::from(2u8) {
+        PrimalityClass::DefinitelyPrime
+    } else if n.is_even() {
+        PrimalityClass::DefinitelyComposite
+    } else {
+        PrimalityClass::NeedsWitnessTest
+    }
+}
+
+fn state_setup(n: BigUint) -> Result<InnerRabinState, MillerRabinResult> {
+    match classify(&n) {
+        PrimalityClass::Trivial(TrivialInput::Zero) => Err(MillerRabinResult::Zero),
+        PrimalityClass::Trivial(TrivialInput::One) => Err(MillerRabinResult::One),
+        PrimalityClass::DefinitelyPrime => Err(MillerRabinResult::CertainPrime),
+        PrimalityClass::DefinitelyComposite => Err(MillerRabinResult::CompositeEven),
+        PrimalityClass::NeedsWitnessTest => Ok(InnerRabinState::new(n)),
     }
-    let state = InnerRabinState::new(n);
-    Ok(state)
 }
 
 // Test if n is prime, using a as the witness, can only show probable primality
@@ -56,6 +281,8 @@ pub enum MillerRabinResult {
     CertainPrime,
     PossiblePrime,
     CompositeWitness(BigUint),
+    // root^2 = 1 mod n but root != +-1, found while testing witness
+    NontrivialSquareRoot { witness: BigUint, root: BigUint },
     CompositeEven,
     Zero,
     One,
@@ -73,6 +300,45 @@ impl MillerRabinResult {
             _ => true,
         }
     }
+
+    // A non trivial factor of n, when the test revealed one
+    pub fn extract_factor(&self, n: &BigUint) -> Option<BigUint> {
+        let factor = match self {
+            Self::NontrivialSquareRoot { root, .. } => (root.clone() - 1u8).gcd(n),
+            Self::CompositeWitness(a) => a.gcd(n),
+            Self::CompositeEven => BigUint::from(2u8),
+            _ => return None,
+        };
+
+        if factor.is_one() || &factor == n {
+            None
+        } else {
+            Some(factor)
+        }
+    }
+
+    // The result together with the number it is about
+    pub fn describe(&self, n: &BigUint) -> String {
+        format!("{}: {}", n, self)
+    }
+}
+
+impl fmt::Display for MillerRabinResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CertainPrime => write!(f, "prime"),
+            Self::PossiblePrime => write!(f, "probably prime"),
+            Self::CompositeWitness(a) => write!(f, "composite (witness: {})", a),
+            Self::NontrivialSquareRoot { witness, root } => write!(
+                f,
+                "composite (witness: {}, square root of one: {})",
+                witness, root
+            ),
+            Self::CompositeEven => write!(f, "composite (even)"),
+            Self::Zero => write!(f, "zero"),
+            Self::One => write!(f, "one"),
+        }
+    }
 }
 
 pub fn miller_rabin(n: BigUint, rounds: usize) -> MillerRabinResult {
@@ -80,6 +346,17 @@ pub fn miller_rabin(n: BigUint, rounds: usize) -> MillerRabinResult {
     miller_rabin_with_randomness(&mut rng, n, rounds)
 }
 
+// Error probability at most 4^-40 for any input
+const DEFAULT_MILLER_RABIN_ROUNDS: usize = 40;
+
+pub fn is_probable_prime(n: &BigUint) -> bool {
+    is_probable_prime_with_rounds(n, DEFAULT_MILLER_RABIN_ROUNDS)
+}
+
+pub fn is_probable_prime_with_rounds(n: &BigUint, rounds: usize) -> bool {
+    miller_rabin(n.clone(), rounds).is_prime()
+}
+
 pub fn deterministic_miller_rabin(n: BigUint) -> MillerRabinResult {
     let poss_state = state_setup(n);
     if let Err(res) = poss_state {
@@ -113,6 +390,11 @@ pub fn miller_rabin_with_randomness<R: rand::Rng>(
     }
     let state = poss_state.unwrap();
 
+    // A small factor is also a witness
+    if let Some(p) = quick_composite_check(&state.n) {
+        return MillerRabinResult::CompositeWitness(p);
+    }
+
     // If the rounds are too many for our range, use deterministic algorithm
     if BigUint::from(rounds) > state.n.clone() - (3u8) {
         return deterministic_miller_rabin_inner(&state);
@@ -152,10 +434,25 @@ fn inner_miller_rabin_step(state: &InnerRabinState, a: BigUint) -> MillerRabinRe
     }
 
     for _ in 1..state.s {
-        x = x.modpow(&BigUint::from(2u8), &state.n);
-        if x == state.n_1 {
+        let y = x.modpow(&BigUint::from(2u8), &state.n);
+        if y == state.n_1 {
             return MillerRabinResult::PossiblePrime;
         }
+        if y.is_one() {
+            return MillerRabinResult::NontrivialSquareRoot {
+                witness: a,
+                root: x,
+            };
+        }
+        x = y;
+    }
+
+    // x = a^((n - 1) / 2), if its square is 1 we still found a root
+    if x.modpow(&BigUint::from(2u8), &state.n).is_one() {
+        return MillerRabinResult::NontrivialSquareRoot {
+            witness: a,
+            root: x,
+        };
     }
 
     MillerRabinResult::CompositeWitness(a)
@@ -163,10 +460,21 @@ fn inner_miller_rabin_step(state: &InnerRabinState, a: BigUint) -> MillerRabinRe
 
 #[cfg(test)]
 mod tests {
-    use num::BigUint;
+    use num::{BigInt, BigUint, Integer};
     use rand::SeedableRng;
 
-    use crate::primes::{miller_rabin_with_randomness, rewrite_n, MillerRabinResult};
+    use crate::fields::{primefields::PrimeField4999, Field};
+    use crate::primes::{
+        classify, deterministic_miller_rabin, euler_criterion, is_probable_prime,
+        is_probable_prime_with_rounds, lucas_uv, miller_rabin_step, miller_rabin_with_randomness,
+        quick_composite_check, rewrite_n, sieve, MillerRabinResult, PrimalityClass, Primes,
+        TrivialInput,
+    };
+
+    const SMALL_PRIMES: [u64; 25] = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89,
+        97,
+    ];
 
     #[test]
     fn representation_tests() {
@@ -218,4 +526,296 @@ mod tests {
             )
         }
     }
+
+    #[test]
+    fn probable_primes() {
+        assert!(!is_probable_prime(&BigUint::from(0u8)));
+        assert!(!is_probable_prime(&BigUint::from(1u8)));
+        assert!(is_probable_prime(&BigUint::from(2u8)));
+
+        for p in SMALL_PRIMES
+            .iter()
+            .chain([65537, 1000003, 2147483647].iter())
+        {
+            assert!(is_probable_prime(&BigUint::from(*p)));
+            assert!(is_probable_prime_with_rounds(&BigUint::from(*p), 1));
+        }
+
+        // Carmichael numbers, then products of primes beyond the wheel
+        let composites = [
+            561u64,
+            1105,
+            1729,
+            2465,
+            2821,
+            6601,
+            8911,
+            3215031751,
+            2003 * 2011,
+            1000003 * 1000033,
+        ];
+        for n in composites.iter() {
+            assert!(!is_probable_prime(&BigUint::from(*n)), "{}", n);
+        }
+    }
+
+    #[test]
+    fn quick_composite_check_tests() {
+        assert_eq!(
+            quick_composite_check(&BigUint::from(99u8)),
+            Some(BigUint::from(3u8))
+        );
+        assert_eq!(
+            quick_composite_check(&BigUint::from(1000u32)),
+            Some(BigUint::from(2u8))
+        );
+        for p in SMALL_PRIMES.iter().chain([1999u64, 2003, 1000003].iter()) {
+            assert_eq!(quick_composite_check(&BigUint::from(*p)), None);
+        }
+        // Smallest factor beyond the wheel
+        assert_eq!(quick_composite_check(&BigUint::from(2003u64 * 2011)), None);
+        assert_eq!(
+            quick_composite_check(&BigUint::from(1999u64 * 2003)),
+            Some(BigUint::from(1999u32))
+        );
+    }
+
+    #[test]
+    fn prefilter_keeps_verdicts() {
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let primes = sieve(5000);
+        for n in 0..5000u64 {
+            let res = miller_rabin_with_randomness(&mut rng, BigUint::from(n), 10);
+            assert_eq!(res.is_prime(), primes.binary_search(&n).is_ok(), "{}", n);
+        }
+
+        let n = BigUint::from(2003u64 * 2011);
+        let res = miller_rabin_with_randomness(&mut rng, n.clone(), 10);
+        assert!(res.is_composite());
+        assert_eq!(res.is_prime(), deterministic_miller_rabin(n).is_prime());
+    }
+
+    #[test]
+    fn classification() {
+        let cases = [
+            (0u32, PrimalityClass::Trivial(TrivialInput::Zero)),
+            (1, PrimalityClass::Trivial(TrivialInput::One)),
+            (2, PrimalityClass::DefinitelyPrime),
+            (3, PrimalityClass::NeedsWitnessTest),
+            (4, PrimalityClass::DefinitelyComposite),
+            (9, PrimalityClass::NeedsWitnessTest),
+        ];
+        for (n, class) in cases.iter() {
+            assert_eq!(classify(&BigUint::from(*n)), *class, "Failure on {}", n);
+        }
+
+        for p in SMALL_PRIMES.iter().skip(1) {
+            assert_eq!(
+                classify(&BigUint::from(*p)),
+                PrimalityClass::NeedsWitnessTest
+            );
+        }
+    }
+
+    #[test]
+    fn extract_factor() {
+        // 2^85 = 32 mod 341 = 11 * 31, and 32^2 = 1
+        let n = BigUint::from(341u32);
+        let res = miller_rabin_step(n.clone(), BigUint::from(2u8));
+        assert_eq!(
+            res,
+            MillerRabinResult::NontrivialSquareRoot {
+                witness: BigUint::from(2u8),
+                root: BigUint::from(32u8)
+            }
+        );
+        assert_eq!(res.extract_factor(&n), Some(BigUint::from(31u8)));
+
+        // Carmichael numbers pass every Fermat test, so coprime witnesses reveal roots
+        for n in [561u32, 1105, 1729, 2465, 2821, 6601, 8911].iter() {
+            let n = BigUint::from(*n);
+            let mut found = 0;
+            for a in 2..100u32 {
+                let res = miller_rabin_step(n.clone(), BigUint::from(a));
+                if let MillerRabinResult::NontrivialSquareRoot { .. } = res {
+                    let factor = res.extract_factor(&n).unwrap();
+                    assert!(n.is_multiple_of(&factor));
+                    assert!(factor > BigUint::from(1u8) && factor < n);
+                    found += 1;
+                }
+            }
+            assert!(found > 0);
+        }
+
+        assert_eq!(
+            MillerRabinResult::CompositeEven.extract_factor(&BigUint::from(10u8)),
+            Some(BigUint::from(2u8))
+        );
+        assert_eq!(
+            MillerRabinResult::PossiblePrime.extract_factor(&BigUint::from(7u8)),
+            None
+        );
+    }
+
+    #[test]
+    fn primes_in_range() {
+        let as_biguints = |v: &[u64]| v.iter().map(|&p| BigUint::from(p)).collect::<Vec<_>>();
+        let range =
+            |lo: u64, hi: u64| super::primes_in_range(&BigUint::from(lo), &BigUint::from(hi));
+
+        assert_eq!(range(10, 30), as_biguints(&[11, 13, 17, 19, 23, 29]));
+        assert!(range(30, 30).is_empty());
+        assert!(range(30, 10).is_empty());
+        assert!(range(0, 2).is_empty());
+        assert_eq!(range(2, 100).len(), 25);
+        assert_eq!(range(0, 100), as_biguints(&SMALL_PRIMES));
+
+        // Several segments
+        assert_eq!(range(0, 200000), as_biguints(&sieve(200000)));
+        assert_eq!(
+            range(100000, 200000),
+            as_biguints(&sieve(200000)[sieve(100000).len()..])
+        );
+
+        // Past the sieve, 2^64 + 13 is the first prime after 2^64
+        let lo = BigUint::from(u64::MAX) + 1u8;
+        let hi = &lo + 20u8;
+        assert_eq!(super::primes_in_range(&lo, &hi), vec![&lo + 13u8]);
+    }
+
+    #[test]
+    fn sieve_tests() {
+        assert!(sieve(0).is_empty());
+        assert!(sieve(2).is_empty());
+        assert_eq!(sieve(3), vec![2]);
+        assert_eq!(sieve(97), SMALL_PRIMES[..24].to_vec());
+        assert_eq!(sieve(100), SMALL_PRIMES.to_vec());
+
+        const ROUNDS: usize = 10;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let primes = sieve(10000);
+        assert_eq!(primes.len(), 1229);
+        for p in primes {
+            assert!(miller_rabin_with_randomness(&mut rng, BigUint::from(p), ROUNDS).is_prime());
+        }
+    }
+
+    fn naive_lucas(p: i64, q: i64, k: usize) -> (BigInt, BigInt) {
+        let (p, q) = (BigInt::from(p), BigInt::from(q));
+        let (mut u, mut u_next) = (BigInt::from(0), BigInt::from(1));
+        let (mut v, mut v_next) = (BigInt::from(2), p.clone());
+        for _ in 0..k {
+            (u, u_next) = (u_next.clone(), &p * u_next - &q * u);
+            (v, v_next) = (v_next.clone(), &p * v_next - &q * v);
+        }
+        (u, v)
+    }
+
+    #[test]
+    fn lucas_tests() {
+        let params = [(1, -1), (3, 2), (1, 2), (-2, 5), (5, -7), (4, 4)];
+        let moduli = [2u64, 7, 10, 64, 4999, 1000003, u64::MAX];
+        for (p, q) in params {
+            for k in 0..64 {
+                let (u, v) = naive_lucas(p, q, k);
+                for n in moduli {
+                    let n_int = BigInt::from(n);
+                    let (u_k, v_k) = lucas_uv(
+                        &BigInt::from(p),
+                        &BigInt::from(q),
+                        &BigUint::from(k),
+                        &BigUint::from(n),
+                    );
+                    assert_eq!(BigInt::from(u_k), u.mod_floor(&n_int));
+                    assert_eq!(BigInt::from(v_k), v.mod_floor(&n_int));
+                }
+            }
+        }
+
+        // Fibonacci and Lucas numbers
+        let (fib, luc) = lucas_uv(
+            &BigInt::from(1),
+            &BigInt::from(-1),
+            &BigUint::from(10u8),
+            &BigUint::from(1000u32),
+        );
+        assert_eq!(fib, BigUint::from(55u8));
+        assert_eq!(luc, BigUint::from(123u8));
+    }
+
+    #[test]
+    fn primes_iterator() {
+        let first: Vec<_> = Primes::new().take(25).collect();
+        let expected: Vec<_> = SMALL_PRIMES.iter().map(|p| BigUint::from(*p)).collect();
+        assert_eq!(first, expected);
+
+        assert_eq!(
+            Primes::new().nth(100),
+            Some(BigUint::from(sieve(1000)[100]))
+        );
+        assert_eq!(Primes::new().nth(100), Some(BigUint::from(547u32)));
+
+        let from_sieve: Vec<_> = sieve(2000).into_iter().map(BigUint::from).collect();
+        let lazy: Vec<_> = Primes::with_seed(42).take(from_sieve.len()).collect();
+        assert_eq!(lazy, from_sieve);
+    }
+
+    #[test]
+    fn display() {
+        let result = miller_rabin_step(BigUint::from(15u8), BigUint::from(2u8));
+        assert_eq!(
+            result,
+            MillerRabinResult::CompositeWitness(BigUint::from(2u8))
+        );
+        assert_eq!(result.to_string(), "composite (witness: 2)");
+        assert_eq!(
+            result.describe(&BigUint::from(15u8)),
+            "15: composite (witness: 2)"
+        );
+
+        let root = MillerRabinResult::NontrivialSquareRoot {
+            witness: BigUint::from(7u8),
+            root: BigUint::from(4u8),
+        };
+        assert_eq!(
+            root.to_string(),
+            "composite (witness: 7, square root of one: 4)"
+        );
+
+        assert_eq!(MillerRabinResult::CertainPrime.to_string(), "prime");
+        assert_eq!(
+            MillerRabinResult::PossiblePrime.to_string(),
+            "probably prime"
+        );
+        assert_eq!(
+            MillerRabinResult::CompositeEven.to_string(),
+            "composite (even)"
+        );
+        assert_eq!(MillerRabinResult::Zero.to_string(), "zero");
+        assert_eq!(MillerRabinResult::One.to_string(), "one");
+        assert_eq!(
+            MillerRabinResult::PossiblePrime.describe(&BigUint::from(97u8)),
+            "97: probably prime"
+        );
+    }
+
+    #[test]
+    fn test_euler_criterion() {
+        let p = BigUint::from(4999u32);
+        for a in 0..10000u32 {
+            let el = PrimeField4999::new(BigUint::from(a));
+            assert_eq!(euler_criterion(&BigUint::from(a), &p), el.legendre());
+        }
+
+        for k in 0..10u32 {
+            assert_eq!(euler_criterion(&(p.clone() * k), &p), 0);
+        }
+
+        // Squares mod 7 are 1, 2 and 4
+        let seven = BigUint::from(7u8);
+        let symbols: Vec<_> = (0..7u8)
+            .map(|a| euler_criterion(&BigUint::from(a), &seven))
+            .collect();
+        assert_eq!(symbols, vec![0, 1, 1, -1, 1, -1, -1]);
+    }
 }
@@ -4,17 +4,50 @@ use num::{BigUint, One, Zero};
 use paste::paste;
 use rand::RngCore;
 use std::fmt;
-use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+// How products of reduced elements are reduced, either remainder (the default) or barrett
+#[macro_export]
+macro_rules! ring_reduction {
+    ($ff:ident, remainder) => {
+        paste! {
+        impl $ff {
+            fn mul_reduced(a: &BigUint, b: &BigUint) -> BigUint {
+                crate::num::modular::mul_mod(a, b, &*[<$ff:upper _MODULO>])
+            }
+        }
+        }
+    };
+    ($ff:ident, barrett) => {
+        paste! {
+        lazy_static! {
+            static ref [<$ff:upper _BARRETT>] : crate::num::barrett::BarrettReducer =
+                crate::num::barrett::BarrettReducer::new([<$ff:upper _MODULO>].clone());
+        }
+
+        impl $ff {
+            fn mul_reduced(a: &BigUint, b: &BigUint) -> BigUint {
+                [<$ff:upper _BARRETT>].reduce(a * b)
+            }
+        }
+        }
+    };
+}
 
 #[macro_export]
 macro_rules! ring_generate {
     ($ff:ident, $mod:expr) => {
+        crate::ring_generate!($ff, $mod, remainder);
+    };
+    ($ff:ident, $mod:expr, $reduction:ident) => {
         paste! {
                     lazy_static! {
                         static ref [<$ff:upper _MODULO>] : BigUint = $mod;
                     }
 
-        #[derive(Debug, Clone, PartialEq, Eq)]
+        crate::ring_reduction!($ff, $reduction);
+
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
         pub struct $ff {
             el: BigUint,
         }
@@ -28,6 +61,67 @@ macro_rules! ring_generate {
             fn new_unchecked(el: BigUint) -> Self {
                 Self { el }
             }
+
+            pub fn is_reduced(&self) -> bool {
+                self.el < *[<$ff:upper _MODULO>]
+            }
+
+            // Invariant check after arithmetic, free in release builds
+            fn assert_reduced(&self) {
+                debug_assert!(self.is_reduced(), "{} is not reduced", self.el);
+            }
+        }
+
+        // Not every generated type needs these
+        #[allow(dead_code)]
+        impl $ff {
+            // None unless el is already reduced
+            pub fn try_new(el: BigUint) -> Option<Self> {
+                if el < *[<$ff:upper _MODULO>] {
+                    Some(Self::new_unchecked(el))
+                } else {
+                    None
+                }
+            }
+
+            // Samples until the element is coprime to the modulus
+            pub fn random_unit(rng: &mut impl RngCore) -> Self {
+                use crate::rings::Ring;
+                if [<$ff:upper _MODULO>].is_one() {
+                    return Self::zero();
+                }
+                loop {
+                    let sample = Self::random(rng);
+                    if num::Integer::gcd(&sample.el, &*[<$ff:upper _MODULO>]).is_one() {
+                        return sample;
+                    }
+                }
+            }
+
+            // Compares the residues, so also correct for elements that are not reduced
+            pub fn eq_mod(&self, other: &Self) -> bool {
+                &self.el % &*[<$ff:upper _MODULO>] == &other.el % &*[<$ff:upper _MODULO>]
+            }
+
+            // None if the element is not reduced, instead of underflowing like Neg
+            pub fn checked_neg(&self) -> Option<Self> {
+                if self.is_reduced() {
+                    Some(Self::new_unchecked(crate::num::modular::neg_mod(&self.el, &*[<$ff:upper _MODULO>])))
+                } else {
+                    None
+                }
+            }
+
+            // self - rhs without consuming or cloning either operand
+            pub fn sub_ref(&self, rhs: &Self) -> Self {
+                Self::new_unchecked(crate::num::modular::sub_mod(&self.el, &rhs.el, &*[<$ff:upper _MODULO>]))
+            }
+
+            // For inputs known to be reduced, only checked in debug builds
+            pub fn from_canonical(el: BigUint) -> Self {
+                debug_assert!(el < *[<$ff:upper _MODULO>], "{} is not reduced", el);
+                Self::new_unchecked(el)
+            }
         }
 
         impl Add for $ff {
@@ -40,28 +134,59 @@ macro_rules! ring_generate {
         impl<'a>  Add<&'a Self> for $ff {
             type Output = Self;
             fn add(self, rhs: &'a Self) -> Self::Output {
-                Self::new(self.el + &rhs.el)
+                let res = Self::new_unchecked(crate::num::modular::add_mod(&self.el, &rhs.el, &*[<$ff:upper _MODULO>]));
+                res.assert_reduced();
+                res
             }
         }
 
         impl AddAssign for $ff {
             fn add_assign(&mut self, rhs: Self) {
-                self.el += rhs.el;
-                self.el %= &* [<$ff:upper _MODULO>];
+                *self += &rhs;
+            }
+        }
+
+        impl<'a> AddAssign<&'a Self> for $ff {
+            fn add_assign(&mut self, rhs: &'a Self) {
+                // add_mod only subtracts the modulus once
+                self.assert_reduced();
+                rhs.assert_reduced();
+                self.el = crate::num::modular::add_mod(&self.el, &rhs.el, &*[<$ff:upper _MODULO>]);
+                self.assert_reduced();
             }
         }
 
         impl Sub for $ff {
             type Output = Self;
             fn sub(self, rhs: Self) -> Self::Output {
-                self + (-rhs)
+                self - &rhs
             }
         }
 
         impl<'a>  Sub<&'a Self> for $ff {
             type Output = Self;
             fn sub(self, rhs: &'a Self) -> Self::Output {
-                self + (-rhs.clone())
+                // Otherwise sub_mod underflows
+                self.assert_reduced();
+                rhs.assert_reduced();
+                let res = Self::new_unchecked(crate::num::modular::sub_mod(&self.el, &rhs.el, &*[<$ff:upper _MODULO>]));
+                res.assert_reduced();
+                res
+            }
+        }
+
+        impl SubAssign for $ff {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self -= &rhs;
+            }
+        }
+
+        impl<'a> SubAssign<&'a Self> for $ff {
+            fn sub_assign(&mut self, rhs: &'a Self) {
+                self.assert_reduced();
+                rhs.assert_reduced();
+                self.el = crate::num::modular::sub_mod(&self.el, &rhs.el, &*[<$ff:upper _MODULO>]);
+                self.assert_reduced();
             }
         }
 
@@ -74,26 +199,34 @@ macro_rules! ring_generate {
 
         impl MulAssign for $ff {
             fn mul_assign(&mut self, rhs: Self) {
-                self.el *= rhs.el;
-                self.el %= &* [<$ff:upper _MODULO>];
+                *self *= &rhs;
+            }
+        }
+
+        impl<'a> MulAssign<&'a Self> for $ff {
+            fn mul_assign(&mut self, rhs: &'a Self) {
+                self.el = Self::mul_reduced(&self.el, &rhs.el);
+                self.assert_reduced();
             }
         }
 
         impl<'a>  Mul<&'a Self> for $ff {
             type Output = Self;
             fn mul(self, rhs: &'a Self) -> Self::Output {
-                Self::new(self.el * &rhs.el)
+                let res = Self::new_unchecked(Self::mul_reduced(&self.el, &rhs.el));
+                res.assert_reduced();
+                res
             }
         }
 
         impl Neg for $ff {
             type Output = Self;
             fn neg(self) -> Self {
-                use crate::rings::Ring;
-                if self.is_zero() {
-                    return Self::zero();
-                }
-                Self::new_unchecked(&*[<$ff:upper _MODULO>] - self.el)
+                // Otherwise the subtraction below underflows
+                self.assert_reduced();
+                let res = Self::new_unchecked(crate::num::modular::neg_mod(&self.el, &*[<$ff:upper _MODULO>]));
+                res.assert_reduced();
+                res
             }
         }
 
@@ -122,3 +255,87 @@ macro_rules! ring_generate {
 }
 
 ring_generate!(IntegersMod425, BigUint::from(425u32));
+crate::ring_poly_tests!(IntegersMod425);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use num::{BigUint, Integer, One};
+    use rand::SeedableRng;
+
+    use super::IntegersMod425;
+
+    #[test]
+    fn random_unit() {
+        const ROUNDS: usize = 1000;
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        let modulus = BigUint::from(425u32);
+        let mut seen = HashSet::new();
+        for _ in 0..ROUNDS {
+            let unit = IntegersMod425::random_unit(&mut rng);
+            assert!(unit.el.gcd(&modulus).is_one());
+            seen.insert(unit);
+        }
+
+        // phi(425) = 320 units, a thousand samples should hit most of them
+        assert!(seen.len() > 250);
+    }
+
+    #[test]
+    fn eq_mod() {
+        let one = IntegersMod425::new(BigUint::one());
+        assert!(IntegersMod425::new(BigUint::from(426u32)).eq_mod(&one));
+        assert_eq!(IntegersMod425::new(BigUint::from(426u32)), one);
+        assert!(!IntegersMod425::new(BigUint::from(2u8)).eq_mod(&one));
+
+        // Only eq_mod sees through an unreduced representative
+        let unreduced = IntegersMod425 {
+            el: BigUint::from(426u32),
+        };
+        assert!(!unreduced.is_reduced());
+        assert!(unreduced.eq_mod(&one));
+        assert!(one.eq_mod(&unreduced));
+        assert_ne!(unreduced, one);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not reduced")]
+    fn add_assign_of_unreduced() {
+        let mut unreduced = IntegersMod425 {
+            el: BigUint::from(849u32),
+        };
+        unreduced += IntegersMod425::new(BigUint::one());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not reduced")]
+    fn sub_of_unreduced() {
+        let unreduced = IntegersMod425 {
+            el: BigUint::from(849u32),
+        };
+        let _ = IntegersMod425::new(BigUint::one()) - unreduced;
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not reduced")]
+    fn sub_assign_of_unreduced() {
+        let mut unreduced = IntegersMod425 {
+            el: BigUint::from(849u32),
+        };
+        unreduced -= IntegersMod425::new(BigUint::one());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "is not reduced")]
+    fn neg_of_unreduced() {
+        let unreduced = IntegersMod425 {
+            el: BigUint::from(426u32),
+        };
+        let _ = -unreduced;
+    }
+}
@@ -1,8 +1,9 @@
 pub mod integers_mod_ring;
 
+use std::marker::PhantomData;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub};
 
-use num::{Integer, Unsigned};
+use num::{bigint::RandBigInt, BigInt, BigUint, Integer, One, Unsigned, Zero};
 use rand::RngCore;
 
 // This is actually need for the macro for tests to compile
@@ -80,12 +81,328 @@ pub trait Ring:
         positive_double_and_add(state, i)
     }
 
+    // Same as positive_pow, but borrows the exponent
+    fn positive_pow_biguint(&self, exponent: &BigUint) -> Self {
+        if self.is_zero() {
+            return Self::zero();
+        }
+
+        let mut acc = Self::one();
+        for i in (0..exponent.bits()).rev() {
+            acc = acc.clone() * &acc;
+            if exponent.bit(i) {
+                acc = acc * self;
+            }
+        }
+        acc
+    }
+
     // Homomorphism Z -> F, Injective if restricted on Z_{char(F)}
     fn integer_embed(i: impl Integer) -> Self {
         Self::one().scale(i)
     }
 }
 
+// Rings with a division with remainder, where the remainder has smaller norm than the divisor
+pub trait EuclideanDomain: Clone {
+    type Norm: Ord;
+
+    // Panics on division by zero
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+    fn is_zero(&self) -> bool;
+    fn norm(&self) -> Self::Norm;
+}
+
+impl EuclideanDomain for BigUint {
+    type Norm = BigUint;
+
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Integer::div_rem(self, other)
+    }
+
+    fn is_zero(&self) -> bool {
+        num::Zero::is_zero(self)
+    }
+
+    fn norm(&self) -> Self::Norm {
+        self.clone()
+    }
+}
+
+// A gcd of a and b, only defined up to units
+pub fn euclidean_gcd<E: EuclideanDomain>(mut a: E, mut b: E) -> E {
+    while !b.is_zero() {
+        let (_, r) = a.div_rem(&b);
+        debug_assert!(r.is_zero() || r.norm() < b.norm());
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// phi(p^k) = p^(k - 1) (p - 1), the order of (Z / p^k)^*, assumes p is prime
+pub fn unit_group_order_prime_power(p: &BigUint, k: u32) -> BigUint {
+    if k == 0 {
+        panic!("Exponent must be positive");
+    }
+    p.pow(k - 1) * (p - 1u8)
+}
+
+// A generator of (Z / p^k)^*, assumes p is prime
+// None when the group is not cyclic, which happens only for 2^k with k >= 3
+pub fn primitive_root_mod_prime_power(p: &BigUint, k: u32) -> Option<BigUint> {
+    if k == 0 {
+        panic!("Exponent must be positive");
+    }
+
+    let two = BigUint::from(2u8);
+    if p == &two {
+        return match k {
+            1 => Some(BigUint::one()),
+            2 => Some(BigUint::from(3u8)),
+            _ => None,
+        };
+    }
+
+    // The smallest primitive root mod p
+    let order = p - 1u8;
+    let factorization = crate::factorization::factorize(order.clone());
+    let mut g = BigUint::one();
+    loop {
+        g += 1u8;
+        if factorization
+            .iter()
+            .all(|(q, _)| !g.modpow(&(&order / q), p).is_one())
+        {
+            break;
+        }
+    }
+
+    // g lifts to a generator mod every p^k unless g^(p - 1) = 1 mod p^2, and then g + p does
+    if k > 1 && g.modpow(&order, &(p * p)).is_one() {
+        g += p;
+    }
+    Some(g)
+}
+
+// The integers, as a ring. BigUint has no negation, so this wraps a BigInt
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BigIntRing(pub BigInt);
+
+impl Neg for BigIntRing {
+    type Output = Self;
+    fn neg(self) -> Self {
+        BigIntRing(-self.0)
+    }
+}
+
+impl Add for BigIntRing {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        BigIntRing(self.0 + rhs.0)
+    }
+}
+
+impl<'a> Add<&'a Self> for BigIntRing {
+    type Output = Self;
+    fn add(self, rhs: &'a Self) -> Self {
+        BigIntRing(self.0 + &rhs.0)
+    }
+}
+
+impl Sub for BigIntRing {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        BigIntRing(self.0 - rhs.0)
+    }
+}
+
+impl<'a> Sub<&'a Self> for BigIntRing {
+    type Output = Self;
+    fn sub(self, rhs: &'a Self) -> Self {
+        BigIntRing(self.0 - &rhs.0)
+    }
+}
+
+impl Mul for BigIntRing {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        BigIntRing(self.0 * rhs.0)
+    }
+}
+
+impl<'a> Mul<&'a Self> for BigIntRing {
+    type Output = Self;
+    fn mul(self, rhs: &'a Self) -> Self {
+        BigIntRing(self.0 * &rhs.0)
+    }
+}
+
+impl AddAssign for BigIntRing {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl MulAssign for BigIntRing {
+    fn mul_assign(&mut self, rhs: Self) {
+        self.0 *= rhs.0;
+    }
+}
+
+impl Ring for BigIntRing {
+    fn zero() -> Self {
+        BigIntRing(BigInt::zero())
+    }
+
+    fn one() -> Self {
+        BigIntRing(BigInt::one())
+    }
+
+    // There is no uniform distribution on Z, this samples 64 bit integers
+    fn random(rng: &mut impl RngCore) -> Self {
+        BigIntRing(rng.gen_bigint(64))
+    }
+}
+
+// The ideal generated by modulus(), together with a choice of representatives modulo it
+pub trait QuotientModulus<R: Ring> {
+    fn modulus() -> R;
+
+    // Congruent elements must have the same representative
+    fn reduce(el: R) -> R;
+
+    // A uniformly random representative
+    fn random_representative(rng: &mut impl RngCore) -> R;
+}
+
+// R / (M::modulus()), every element is kept as its representative
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuotientRing<R, M> {
+    el: R,
+    modulus: PhantomData<M>,
+}
+
+impl<R, M> QuotientRing<R, M>
+where
+    R: Ring,
+    M: QuotientModulus<R>,
+{
+    pub fn new(el: R) -> Self {
+        QuotientRing {
+            el: M::reduce(el),
+            modulus: PhantomData,
+        }
+    }
+
+    pub fn representative(&self) -> &R {
+        &self.el
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> Neg for QuotientRing<R, M> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.el)
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> Add for QuotientRing<R, M> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.el + rhs.el)
+    }
+}
+
+impl<'a, R: Ring, M: QuotientModulus<R>> Add<&'a Self> for QuotientRing<R, M> {
+    type Output = Self;
+    fn add(self, rhs: &'a Self) -> Self {
+        Self::new(self.el + &rhs.el)
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> Sub for QuotientRing<R, M> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.el - rhs.el)
+    }
+}
+
+impl<'a, R: Ring, M: QuotientModulus<R>> Sub<&'a Self> for QuotientRing<R, M> {
+    type Output = Self;
+    fn sub(self, rhs: &'a Self) -> Self {
+        Self::new(self.el - &rhs.el)
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> Mul for QuotientRing<R, M> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(self.el * rhs.el)
+    }
+}
+
+impl<'a, R: Ring, M: QuotientModulus<R>> Mul<&'a Self> for QuotientRing<R, M> {
+    type Output = Self;
+    fn mul(self, rhs: &'a Self) -> Self {
+        Self::new(self.el * &rhs.el)
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> AddAssign for QuotientRing<R, M> {
+    fn add_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::replace(&mut self.el, R::zero());
+        self.el = M::reduce(lhs + rhs.el);
+    }
+}
+
+impl<R: Ring, M: QuotientModulus<R>> MulAssign for QuotientRing<R, M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::replace(&mut self.el, R::zero());
+        self.el = M::reduce(lhs * rhs.el);
+    }
+}
+
+impl<R, M> Ring for QuotientRing<R, M>
+where
+    R: Ring,
+    M: QuotientModulus<R> + Clone + Eq,
+{
+    fn zero() -> Self {
+        Self::new(R::zero())
+    }
+
+    fn one() -> Self {
+        Self::new(R::one())
+    }
+
+    fn random(rng: &mut impl RngCore) -> Self {
+        Self::new(M::random_representative(rng))
+    }
+}
+
+// Z / 425, the same ring as IntegersMod425
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mod425;
+
+impl QuotientModulus<BigIntRing> for Mod425 {
+    fn modulus() -> BigIntRing {
+        BigIntRing(BigInt::from(425))
+    }
+
+    fn reduce(el: BigIntRing) -> BigIntRing {
+        BigIntRing(el.0.mod_floor(&Self::modulus().0))
+    }
+
+    fn random_representative(rng: &mut impl RngCore) -> BigIntRing {
+        BigIntRing(rng.gen_bigint_range(&BigInt::zero(), &Self::modulus().0))
+    }
+}
+
+pub type QuotientMod425 = QuotientRing<BigIntRing, Mod425>;
+
+crate::ring_tests!(QuotientMod425);
+
 #[macro_export]
 macro_rules! ring_tests {
     ($rr:ident) => {
@@ -94,6 +411,7 @@ macro_rules! ring_tests {
             mod [< $rr:snake _ring_tests >] {
                 use super::$rr;
                 use crate::rings::Ring;
+                use num::bigint::RandBigInt;
                 use rand::SeedableRng;
 
                 #[test]
@@ -136,6 +454,22 @@ macro_rules! ring_tests {
                     }
                 }
 
+                #[test]
+                fn positive_pow_biguint() {
+                    const NUM_ELEMENTS: usize = 100;
+                    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+                    for _ in 0..NUM_ELEMENTS {
+                        let el = $rr::random(&mut rng);
+                        for i in 0..64u32 {
+                            let exp = num::BigUint::from(i);
+                            assert_eq!(el.positive_pow_biguint(&exp), el.positive_pow(i));
+                        }
+
+                        let exp = rng.gen_biguint(512);
+                        assert_eq!(el.positive_pow_biguint(&exp), el.positive_pow(exp.clone()));
+                    }
+                }
+
                 #[test]
                 fn distributivity() {
                     const NUM_ELEMENTS: usize = 1000;
@@ -153,3 +487,103 @@ macro_rules! ring_tests {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use num::{BigInt, BigUint, Integer, One};
+    use rand::SeedableRng;
+
+    use super::{euclidean_gcd, unit_group_order_prime_power, BigIntRing, QuotientMod425, Ring};
+    use crate::gcd::egcd;
+    use crate::rings::integers_mod_ring::IntegersMod425;
+
+    #[test]
+    fn primitive_root_mod_prime_power() {
+        fn generated(g: &BigUint, modulus: &BigUint) -> HashSet<BigUint> {
+            let mut powers = HashSet::new();
+            let mut x = BigUint::one();
+            loop {
+                x = x * g % modulus;
+                if !powers.insert(x.clone()) {
+                    return powers;
+                }
+            }
+        }
+
+        for &(p, k) in [(7u32, 1), (7, 2), (5, 3), (2, 1), (2, 2), (3, 4)].iter() {
+            let p = BigUint::from(p);
+            let modulus = p.pow(k);
+            let g = super::primitive_root_mod_prime_power(&p, k).unwrap();
+            let units: HashSet<_> = num::range(BigUint::one(), modulus.clone())
+                .filter(|x| x.gcd(&modulus).is_one())
+                .collect();
+            assert_eq!(generated(&g, &modulus), units);
+            assert_eq!(
+                BigUint::from(units.len()),
+                unit_group_order_prime_power(&p, k)
+            );
+        }
+
+        assert_eq!(
+            super::primitive_root_mod_prime_power(&BigUint::from(7u8), 1),
+            Some(BigUint::from(3u8))
+        );
+        for k in 3..6 {
+            assert_eq!(
+                super::primitive_root_mod_prime_power(&BigUint::from(2u8), k),
+                None
+            );
+        }
+
+        // 5 is the smallest primitive root mod 40487, but 5^40486 = 1 mod 40487^2
+        let p = BigUint::from(40487u32);
+        let modulus = &p * &p;
+        let g = super::primitive_root_mod_prime_power(&p, 2).unwrap();
+        assert_eq!(g, BigUint::from(5u8) + &p);
+        let order = unit_group_order_prime_power(&p, 2);
+        for &q in [2u32, 31, 653, 40487].iter() {
+            assert!(!g.modpow(&(&order / q), &modulus).is_one());
+        }
+        assert!(g.modpow(&order, &modulus).is_one());
+    }
+
+    #[test]
+    fn quotient_matches_integers_mod_425() {
+        let embed = |x: &IntegersMod425| {
+            QuotientMod425::new(BigIntRing(BigInt::from(
+                x.to_string().parse::<u32>().unwrap(),
+            )))
+        };
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(42);
+        for _ in 0..1000 {
+            let (a, b) = (
+                IntegersMod425::random(&mut rng),
+                IntegersMod425::random(&mut rng),
+            );
+            let (x, y) = (embed(&a), embed(&b));
+            assert_eq!(embed(&(a.clone() + &b)), x.clone() + &y);
+            assert_eq!(embed(&(a.clone() - &b)), x.clone() - &y);
+            assert_eq!(embed(&(a.clone() * &b)), x.clone() * &y);
+            assert_eq!(embed(&-a), -x);
+        }
+
+        assert_eq!(
+            QuotientMod425::new(BigIntRing(BigInt::from(-1))).representative(),
+            &BigIntRing(BigInt::from(424))
+        );
+        assert_eq!(QuotientMod425::integer_embed(426), QuotientMod425::one());
+    }
+
+    #[test]
+    fn euclidean_gcd_integers() {
+        for a in 0..128u32 {
+            for b in 0..128u32 {
+                let (a, b) = (BigUint::from(a), BigUint::from(b));
+                assert_eq!(euclidean_gcd(a.clone(), b.clone()), egcd(a, b).d);
+            }
+        }
+    }
+}